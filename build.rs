@@ -0,0 +1,69 @@
+//! Generates the list of solutions that `src/template/registry.rs` includes from the binaries in `src/bin`,
+//! and records the compiler and profile of the build for the timing history.
+use std::{env, fs, path::Path, process::Command};
+
+fn main() {
+    println!("cargo::rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("registry.rs");
+
+    // NOTE: dhat installs a global allocator per binary, which would clash once the days share the library.
+    let enabled = env::var_os("CARGO_FEATURE_REGISTRY").is_some()
+        && env::var_os("CARGO_FEATURE_DHAT_HEAP").is_none();
+
+    // NOTE: binaries are named after their year and day, e.g. `src/bin/2025-01.rs`.
+    let mut days: Vec<String> = if enabled {
+        fs::read_dir(Path::new(&manifest_dir).join("src/bin"))
+            .unwrap()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let stem = path.file_stem()?.to_str()?;
+                let is_day = path.extension()? == "rs"
+                    && stem.len() == 7
+                    && stem.char_indices().all(|(i, c)| match i {
+                        4 => c == '-',
+                        _ => c.is_ascii_digit(),
+                    });
+                is_day.then(|| stem.to_string())
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+    days.sort();
+
+    let mut code = String::new();
+
+    let module = |day: &str| format!("day_{}", day.replace('-', "_"));
+
+    for day in &days {
+        code.push_str(&format!(
+            "#[path = {:?}]\n#[allow(dead_code)]\nmod {};\n\n",
+            Path::new(&manifest_dir).join(format!("src/bin/{day}.rs")),
+            module(day)
+        ));
+    }
+
+    code.push_str("/// Every solution that is part of this build, sorted by year and day.\n");
+    code.push_str("pub const SOLUTIONS: &[Solution] = &[\n");
+    for day in &days {
+        code.push_str(&format!("    {}::SOLUTION,\n", module(day)));
+    }
+    code.push_str("];\n");
+
+    fs::write(out_path, code).unwrap();
+
+    // NOTE: recorded with every entry of the timing history, see `src/template/history.rs`.
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    println!("cargo::rustc-env=AOC_RUSTC_VERSION={rustc_version}");
+    println!(
+        "cargo::rustc-env=AOC_PROFILE={}",
+        env::var("PROFILE").unwrap()
+    );
+}
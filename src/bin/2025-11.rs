@@ -80,17 +80,17 @@ pub fn part_two(input: &str) -> Option<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use advent_of_code::template::read_file_part;
+    use advent_of_code::template::{Part, read_file_part};
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&read_file_part("examples", DAY, 1));
+        let result = part_one(&read_file_part("examples", DAY, Part::One));
         assert_eq!(result, Some(5));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&read_file_part("examples", DAY, 2));
+        let result = part_two(&read_file_part("examples", DAY, Part::Two));
         assert_eq!(result, Some(2));
     }
 }
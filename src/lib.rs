@@ -1,3 +1,6 @@
+// Lets the solutions in `src/bin` refer to `advent_of_code::...` when they are compiled into the registry.
+extern crate self as advent_of_code;
+
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
@@ -1,81 +1,192 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    accept, all, download, examples, progress, read, reset, scaffold, solve, time,
+};
+use advent_of_code::template::{Day, DaySelector};
 use args::{AppArguments, parse};
+use std::process;
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::{commands::today, unlock::SystemClock};
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        DEFAULT_YEAR, Day, DaySelector, Part,
+        commands::time::DEFAULT_THRESHOLD,
+        runner::{BenchConfig, RunOptions, parse_seconds},
+    };
+    use std::{env, process, time::Duration};
 
     pub enum AppArguments {
+        Accept {
+            day: Day,
+            part: Option<Part>,
+            input: Option<String>,
+        },
         Download {
+            days: DaySelector,
+        },
+        Examples {
             day: Day,
+            block: Option<usize>,
+            part: Option<Part>,
+            overwrite: bool,
+        },
+        Progress {
+            year: u16,
+            sync: bool,
         },
         Read {
             day: Day,
         },
-        Scaffold {
+        Reset {
             day: Day,
+            template: Option<String>,
+            dry_run: bool,
+        },
+        Scaffold {
+            days: DaySelector,
             download: bool,
+            examples: bool,
             overwrite: bool,
+            template: Option<String>,
+            dry_run: bool,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            allocs: bool,
+            submit: Option<Part>,
+            options: RunOptions,
         },
         All {
-            release: bool,
+            days: DaySelector,
+            timeout: Option<Duration>,
         },
         Time {
+            year: u16,
             all: bool,
-            day: Option<Day>,
+            days: Option<DaySelector>,
             store: bool,
+            compare: Option<f64>,
+            timeout: Option<Duration>,
+            bench: BenchConfig,
+        },
+        History {
+            day: Day,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+        let subcommand = args.subcommand()?;
+        let year = parse_year(&mut args)?;
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                // NOTE: `all` used to build the solutions in release mode with `--release`. They are
+                // now compiled into the runner, which the `all` alias already builds in release mode.
+                if args.contains("--release") {
+                    eprintln!(
+                        "Warning: `--release` is deprecated for `all` and has no effect, `cargo all` always runs an optimized build."
+                    );
+                }
+                let timeout = args.opt_value_from_fn("--timeout", parse_seconds)?;
+
+                AppArguments::All {
+                    days: parse_selector(&mut args, year)?.unwrap_or(DaySelector::all(year)),
+                    timeout,
+                }
+            }
+            Some("time") if args.contains("--history") => AppArguments::History {
+                day: parse_day(&mut args, year)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+                let timeout = args.opt_value_from_fn("--timeout", parse_seconds)?;
+                let bench = BenchConfig::parse_args(&mut args)?;
 
                 AppArguments::Time {
+                    year,
                     all,
-                    day: args.opt_free_from_str()?,
+                    days: parse_selector(&mut args, year)?,
                     store,
+                    compare: compare.then(|| threshold.unwrap_or(DEFAULT_THRESHOLD)),
+                    timeout,
+                    bench,
                 }
             }
+            Some("accept") => AppArguments::Accept {
+                day: parse_day(&mut args, year)?,
+                part: args.opt_value_from_str("--part")?,
+                input: args.opt_value_from_str("--input")?,
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                days: parse_required_selector(&mut args, year)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                day: parse_day(&mut args, year)?,
+                block: args.opt_value_from_str("--block")?,
+                part: args.opt_value_from_str("--part")?,
+                overwrite: args.contains("--overwrite"),
+            },
+            Some("progress") => AppArguments::Progress {
+                year,
+                sync: args.contains("--sync"),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                day: parse_day(&mut args, year)?,
+            },
+            Some("reset") => AppArguments::Reset {
+                day: parse_day(&mut args, year)?,
+                template: args.opt_value_from_str("--template")?,
+                dry_run: args.contains("--dry-run"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                days: parse_required_selector(&mut args, year)?,
                 download: args.contains("--download"),
+                examples: args.contains("--examples"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
+                dry_run: args.contains("--dry-run"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let dhat = args.contains("--dhat");
+                let allocs = args.contains("--allocs");
+                if dhat && allocs {
+                    return Err(
+                        "`--dhat` and `--allocs` cannot be combined, both install a global allocator."
+                            .into(),
+                    );
+                }
+
+                AppArguments::Solve {
+                    day: parse_day(&mut args, year)?,
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    dhat,
+                    allocs,
+                    options: RunOptions {
+                        time: args.contains("--time"),
+                        json: false,
+                        timeout: args.opt_value_from_fn("--timeout", parse_seconds)?,
+                        bench: BenchConfig::parse_args(&mut args)?,
+                        input: args.opt_value_from_str("--input")?,
+                    },
+                }
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -93,6 +204,81 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// The year selected with `--year`, defaults to `AOC_YEAR`.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<u16, Box<dyn std::error::Error>> {
+        let year = match args.opt_value_from_str("--year")? {
+            Some(year) => year,
+            None => env::var("AOC_YEAR")
+                .ok()
+                .and_then(|year| year.parse().ok())
+                .unwrap_or(DEFAULT_YEAR),
+        };
+
+        if year < 2015 {
+            return Err("`--year` must be 2015 or later.".into());
+        }
+
+        Ok(year)
+    }
+
+    fn parse_day(
+        args: &mut pico_args::Arguments,
+        year: u16,
+    ) -> Result<Day, Box<dyn std::error::Error>> {
+        let day: String = args.free_from_str()?;
+        Ok(Day::parse(&day, year)?)
+    }
+
+    fn parse_selector(
+        args: &mut pico_args::Arguments,
+        year: u16,
+    ) -> Result<Option<DaySelector>, Box<dyn std::error::Error>> {
+        Ok(args
+            .opt_free_from_str::<String>()?
+            .map(|days| DaySelector::parse(&days, year))
+            .transpose()?)
+    }
+
+    fn parse_required_selector(
+        args: &mut pico_args::Arguments,
+        year: u16,
+    ) -> Result<DaySelector, Box<dyn std::error::Error>> {
+        let days: String = args.free_from_str()?;
+        Ok(DaySelector::parse(&days, year)?)
+    }
+}
+
+fn selected_days(selector: &DaySelector) -> Vec<Day> {
+    let days = selector.days();
+    if days.is_empty() {
+        eprintln!("No days match `{selector}`.");
+        process::exit(1);
+    }
+    days
+}
+
+/// Runs `step` for every day, reporting a failed day and continuing with the next one.
+/// Exits with an error once all days ran if any of them failed.
+fn for_each_day(days: &[Day], mut step: impl FnMut(Day) -> Result<(), String>) {
+    let mut failed = vec![];
+
+    for &day in days {
+        if let Err(e) = step(day) {
+            eprintln!("{e}");
+            failed.push(day.to_string());
+        }
+    }
+
+    if failed.is_empty() {
+        return;
+    }
+
+    if days.len() > 1 {
+        eprintln!("---");
+        eprintln!("Failed for days {}.", failed.join(", "));
+    }
+    process::exit(1);
 }
 
 fn main() {
@@ -102,43 +288,71 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::Accept { day, part, input } => accept::handle(day, part, input),
+            AppArguments::All { days, timeout } => all::handle(&days, timeout),
+            AppArguments::Time {
+                year,
+                days,
+                all,
+                store,
+                compare,
+                timeout,
+                bench,
+            } => time::handle(year, days, all, store, compare, timeout, bench),
+            AppArguments::History { day } => time::handle_history(day),
+            AppArguments::Download { days } => {
+                for_each_day(&selected_days(&days), download::handle);
+            }
+            AppArguments::Examples {
+                day,
+                block,
+                part,
+                overwrite,
+            } => {
+                if let Err(e) = examples::handle(day, block, part, overwrite) {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+            }
+            AppArguments::Progress { year, sync } => progress::handle(year, sync),
             AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::Reset {
                 day,
+                template,
+                dry_run,
+            } => reset::handle(day, template.as_deref(), dry_run),
+            AppArguments::Scaffold {
+                days,
                 download,
+                examples,
                 overwrite,
+                template,
+                dry_run,
             } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+                for_each_day(&selected_days(&days), |day| {
+                    scaffold::handle(day, overwrite, template.as_deref(), dry_run)?;
+                    if dry_run {
+                        return Ok(());
+                    }
+                    if download {
+                        download::handle(day)?;
+                    }
+                    if examples {
+                        examples::handle(day, None, None, false)?;
+                    }
+                    Ok(())
+                });
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                allocs,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                options,
+            } => solve::handle(day, release, dhat, allocs, submit, &options),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { wait } => today::handle(&SystemClock, wait),
         },
     };
 }
@@ -0,0 +1,212 @@
+/// Known answers for the real puzzle inputs, used to catch regressions when refactoring a solution.
+/// Answers are stored per day as `data/{year}/answers/{day}.json`, or
+/// `data/{year}/answers/{day}/{name}.json` for the named input `data/{year}/inputs/{day}/{name}.txt`.
+use std::{collections::HashMap, fmt::Display, fs, io, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Part, data_dir, inputs::DEFAULT_INPUT};
+
+/// Accepted answers of a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Outcome of comparing a part's output against its known answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The output matches the known answer.
+    Correct,
+    /// The output differs from the known answer.
+    Incorrect { expected: String },
+    /// There is no known answer for this part yet.
+    Unknown,
+}
+
+impl Answers {
+    /// Path of the answers of an input, `None` refers to the default input.
+    pub fn path(day: Day, input: Option<&str>) -> String {
+        let dir = data_dir(day.year());
+        match input {
+            Some(name) if name != DEFAULT_INPUT => format!("{dir}/answers/{day}/{name}.json"),
+            _ => format!("{dir}/answers/{day}.json"),
+        }
+    }
+
+    /// Read the known answers of an input. If not present, returns empty answers.
+    pub fn read(day: Day, input: Option<&str>) -> Self {
+        fs::read_to_string(Self::path(day, input))
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Store the known answers of an input, creating the parent directory if needed.
+    pub fn store(&self, day: Day, input: Option<&str>) -> Result<(), io::Error> {
+        let path = Self::path(day, input);
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_1.as_deref(),
+            Part::Two => self.part_2.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, answer: &str) {
+        match part {
+            Part::One => self.part_1 = Some(answer.into()),
+            Part::Two => self.part_2 = Some(answer.into()),
+        }
+    }
+
+    /// Compare `answer` against the known answer of `part`.
+    pub fn check(&self, part: Part, answer: &str) -> Verdict {
+        match self.get(part) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Incorrect {
+                expected: expected.into(),
+            },
+        }
+    }
+}
+
+/// Record `answer` as the known answer of a part for the given input.
+pub fn accept(day: Day, input: Option<&str>, part: Part, answer: &str) -> Result<(), io::Error> {
+    let mut answers = Answers::read(day, input);
+    answers.set(part, answer);
+    answers.store(day, input)
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect { .. } => "incorrect",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                answer.clone().map_or(JsonValue::Null, JsonValue::String),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let answer = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("expected `{key}` to be null or string.")),
+        };
+
+        Ok(Answers {
+            part_1: answer("part_1")?,
+            part_2: answer("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Verdict};
+    use crate::{day, template::Part};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn checks_answers() {
+        let answers = Answers {
+            part_1: Some("42".into()),
+            part_2: None,
+        };
+
+        assert_eq!(answers.check(Part::One, "42"), Verdict::Correct);
+        assert_eq!(
+            answers.check(Part::One, "41"),
+            Verdict::Incorrect {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(answers.check(Part::Two, "42"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        answers.set(Part::Two, "7");
+        assert_eq!(answers.get(Part::One), None);
+        assert_eq!(answers.get(Part::Two), Some("7"));
+    }
+
+    #[test]
+    fn stores_answers_per_input() {
+        let day = day!(9).with_year(2024);
+        assert_eq!(Answers::path(day, None), "data/2024/answers/09.json");
+        assert_eq!(
+            Answers::path(day, Some("default")),
+            "data/2024/answers/09.json"
+        );
+        assert_eq!(
+            Answers::path(day, Some("alice")),
+            "data/2024/answers/09/alice.json"
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = Answers {
+            part_1: Some("1\n2".into()),
+            part_2: None,
+        };
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn handles_partial_json() {
+        let answers = Answers::try_from(r#"{ "part_2": "9" }"#.to_string()).unwrap();
+        assert_eq!(answers.part_1, None);
+        assert_eq!(answers.part_2, Some("9".into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_json() {
+        Answers::try_from(r#"{ "part_1": 1 }"#.to_string()).unwrap();
+    }
+}
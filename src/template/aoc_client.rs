@@ -0,0 +1,598 @@
+/// Built-in client for the Advent of Code website, used to download inputs, read puzzles and submit answers.
+///
+/// The client is configured via environment variables:
+///  - `AOC_SESSION`: the session cookie. If not set, it is read from the file `AOC_SESSION_FILE`,
+///    which defaults to `~/.adventofcode.session` (the same file aoc-cli uses).
+///  - `AOC_BASE_URL`: defaults to `https://adventofcode.com`, e.g. to test against a local server.
+///  - `AOC_BACKEND`: set to `aoc-cli` to shell out to aoc-cli instead.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{Day, Part, aoc_cli};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!(
+    "advent_of_code template ",
+    env!("CARGO_PKG_VERSION"),
+    " (built-in client)"
+);
+
+#[derive(Debug)]
+pub enum AocError {
+    /// No session cookie was found.
+    MissingSession,
+    /// The request failed or the server responded with an error status.
+    Request(String),
+    Io(io::Error),
+    AocCli(aoc_cli::AocCommandError),
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create \"~/.adventofcode.session\"."
+            ),
+            AocError::Request(e) => write!(f, "request failed: {e}"),
+            AocError::Io(e) => write!(f, "{e}"),
+            AocError::AocCli(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for AocError {
+    fn from(e: ureq::Error) -> Self {
+        AocError::Request(e.to_string())
+    }
+}
+
+/// Whether a wrong answer was too high or too low, if the website said so.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// Outcome of submitting an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitResult {
+    Correct,
+    /// A wrong answer, with the penalty to wait before the next attempt if known.
+    Incorrect {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    /// An answer was submitted too recently, with the remaining time to wait if known.
+    TooRecent {
+        wait: Option<Duration>,
+    },
+    /// The part was already solved, or its prerequisites are not.
+    WrongLevel,
+    /// A response that could not be classified, as plain text.
+    Unknown(String),
+}
+
+impl Display for SubmitResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitResult::Correct => write!(f, "That's the right answer! ⭐️"),
+            SubmitResult::Incorrect { hint, wait } => {
+                write!(f, "That's not the right answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, ", it is too high.")?,
+                    Some(Hint::TooLow) => write!(f, ", it is too low.")?,
+                    None => write!(f, ".")?,
+                }
+                match wait {
+                    Some(wait) => write!(f, " Wait {wait:?} before trying again."),
+                    None => Ok(()),
+                }
+            }
+            SubmitResult::TooRecent { wait: Some(wait) } => {
+                write!(
+                    f,
+                    "You submitted too recently, wait {wait:?} and try again."
+                )
+            }
+            SubmitResult::TooRecent { wait: None } => {
+                write!(f, "You submitted too recently, wait a bit and try again.")
+            }
+            SubmitResult::WrongLevel => write!(
+                f,
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            SubmitResult::Unknown(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// Client for the Advent of Code website.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// Create a client from the environment, see the module documentation.
+    pub fn from_env() -> Result<Self, AocError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) => session,
+            Err(_) => session_file()
+                .and_then(|path| fs::read_to_string(path).ok())
+                .ok_or(AocError::MissingSession)?,
+        };
+
+        let session = session.trim();
+        if session.is_empty() {
+            return Err(AocError::MissingSession);
+        }
+
+        Ok(Self::new(&base_url, session))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, day.year(), day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocError> {
+        let body = self
+            .agent
+            .get(url)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .call()?
+            .body_mut()
+            .read_to_string()?;
+        Ok(body)
+    }
+
+    /// Downloads the puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Downloads the puzzle description of a day, converted to markdown.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocError> {
+        let html = self.get(&self.day_url(day))?;
+        Ok(puzzle_to_markdown(&html))
+    }
+
+    /// Downloads the calendar of a year, i.e. the page that shows the stars of each day.
+    pub fn calendar(&self, year: u16) -> Result<String, AocError> {
+        self.get(&format!("{}/{year}", self.base_url))
+    }
+
+    /// Submits the answer of a part.
+    pub fn submit(&self, day: Day, part: Part, answer: &str) -> Result<SubmitResult, AocError> {
+        let level = part.to_string();
+        let html = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", level.as_str()), ("answer", answer)])?
+            .body_mut()
+            .read_to_string()?;
+        Ok(parse_submit_response(&html))
+    }
+}
+
+fn session_file() -> Option<PathBuf> {
+    if let Ok(path) = env::var("AOC_SESSION_FILE") {
+        return Some(PathBuf::from(path));
+    }
+
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| Path::new(&home).join(".adventofcode.session"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Which implementation talks to the website.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// The built-in [`Client`].
+    Native,
+    /// The external `aoc` command, selected with `AOC_BACKEND=aoc-cli`.
+    AocCli,
+}
+
+impl Backend {
+    pub fn from_env() -> Self {
+        match env::var("AOC_BACKEND").as_deref() {
+            Ok("aoc-cli") => Backend::AocCli,
+            _ => Backend::Native,
+        }
+    }
+}
+
+fn check_aoc_cli() -> Result<(), AocError> {
+    aoc_cli::check().map_err(AocError::AocCli)
+}
+
+/// Download the input and puzzle description of a day to `data/inputs` and `data/puzzles`.
+pub fn download(day: Day) -> Result<(), AocError> {
+    if Backend::from_env() == Backend::AocCli {
+        check_aoc_cli()?;
+        return aoc_cli::download(day).map(|_| ()).map_err(AocError::AocCli);
+    }
+
+    let client = Client::from_env()?;
+    let input_path = aoc_cli::get_input_path(day);
+    let puzzle_path = aoc_cli::get_puzzle_path(day);
+
+    let input = client.input(day)?;
+    let puzzle = client.puzzle(day)?;
+
+    write_file(&input_path, &input)?;
+    write_file(&puzzle_path, &puzzle)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Print the puzzle description of a day and save it to `data/puzzles`.
+pub fn read(day: Day) -> Result<(), AocError> {
+    if Backend::from_env() == Backend::AocCli {
+        check_aoc_cli()?;
+        return aoc_cli::read(day).map(|_| ()).map_err(AocError::AocCli);
+    }
+
+    let puzzle = Client::from_env()?.puzzle(day)?;
+    write_file(&aoc_cli::get_puzzle_path(day), &puzzle)?;
+    println!("{puzzle}");
+    Ok(())
+}
+
+/// Submit the answer of a part.
+pub fn submit(day: Day, part: Part, answer: &str) -> Result<SubmitResult, AocError> {
+    if Backend::from_env() == Backend::AocCli {
+        check_aoc_cli()?;
+        let output = aoc_cli::submit(day, part, answer).map_err(AocError::AocCli)?;
+        return Ok(parse_submit_response(&String::from_utf8_lossy(
+            &output.stdout,
+        )));
+    }
+
+    Client::from_env()?.submit(day, part, answer)
+}
+
+fn write_file(path: &str, content: &str) -> Result<(), io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Classifies the response to a submission. Accepts the HTML page as well as plain text.
+pub fn parse_submit_response(response: &str) -> SubmitResult {
+    let text = html_to_text(extract_article(response).unwrap_or(response));
+
+    if text.contains("That's the right answer") {
+        SubmitResult::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        SubmitResult::Incorrect {
+            hint,
+            wait: parse_penalty(&text),
+        }
+    } else if text.contains("You gave an answer too recently") {
+        SubmitResult::TooRecent {
+            wait: parse_wait(&text),
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        SubmitResult::WrongLevel
+    } else {
+        SubmitResult::Unknown(text.trim().to_string())
+    }
+}
+
+/// Parses the remaining wait time from e.g. `You have 1m 34s left to wait.`
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    text[start..end]
+        .split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Parses the penalty of a wrong answer from e.g. `Please wait one minute before trying again.`
+fn parse_penalty(text: &str) -> Option<Duration> {
+    let start = text.find("wait ")? + "wait ".len();
+    let mut words = text[start..].split_whitespace();
+
+    let amount: u64 = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        "ten" => 10,
+        x => x.parse().ok()?,
+    };
+
+    let unit = words.next()?;
+    let seconds = if unit.starts_with("second") {
+        1
+    } else if unit.starts_with("minute") {
+        60
+    } else if unit.starts_with("hour") {
+        3600
+    } else {
+        return None;
+    };
+
+    Some(Duration::from_secs(amount * seconds))
+}
+
+fn extract_article(html: &str) -> Option<&str> {
+    let start = html.find("<article")?;
+    let end = html[start..].find("</article>")? + start + "</article>".len();
+    Some(&html[start..end])
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+pub(crate) fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        match rest[start..].find('>') {
+            Some(end) => rest = &rest[start + end + 1..],
+            None => rest = "",
+        }
+    }
+
+    text.push_str(rest);
+    decode_entities(&text)
+}
+
+/// Converts the puzzle descriptions (`<article class="day-desc">`) of a puzzle page to markdown.
+pub fn puzzle_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article class=\"day-desc\">") {
+        let article = &rest[start..];
+        let end = article.find("</article>").unwrap_or(article.len());
+        markdown.push_str(&article_to_markdown(&article[..end]));
+        rest = &article[end..];
+    }
+
+    markdown.trim().to_string() + "\n"
+}
+
+fn article_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut rest = html;
+    let mut in_pre = false;
+
+    while let Some(start) = rest.find('<') {
+        markdown.push_str(&decode_entities(&rest[..start]));
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+
+        let tag = &rest[start + 1..start + end];
+        let name = tag
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match name.as_str() {
+            "h2" => markdown.push_str("## "),
+            "/h2" | "/p" | "/ul" => markdown.push_str("\n\n"),
+            "pre" => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            "/pre" => {
+                in_pre = false;
+                markdown.push_str("```\n\n");
+            }
+            "code" | "/code" if !in_pre => markdown.push('`'),
+            "em" | "/em" if !in_pre => markdown.push('*'),
+            "li" => markdown.push_str("- "),
+            "/li" => markdown.push('\n'),
+            _ => {}
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+
+    markdown.push_str(&decode_entities(rest));
+    markdown
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use super::{Client, Hint, SubmitResult, parse_submit_response, puzzle_to_markdown};
+    use crate::day;
+
+    #[test]
+    fn parses_submit_responses() {
+        let html =
+            |text: &str| format!("<html><main><article><p>{text}</p></article></main></html>");
+
+        assert_eq!(
+            parse_submit_response(&html(
+                "That's the right answer! You are one gold star closer."
+            )),
+            SubmitResult::Correct
+        );
+        assert_eq!(
+            parse_submit_response(&html(
+                "That's not the right answer; your answer is too high.  If you're stuck, ..."
+            )),
+            SubmitResult::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait: None,
+            }
+        );
+        assert_eq!(
+            parse_submit_response(&html(
+                "That's not the right answer.  If you're stuck, ... Please wait one minute before trying again."
+            )),
+            SubmitResult::Incorrect {
+                hint: None,
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse_submit_response(&html(
+                "That's not the right answer; your answer is too low. ... please wait 5 minutes before trying again."
+            )),
+            SubmitResult::Incorrect {
+                hint: Some(Hint::TooLow),
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+        assert_eq!(
+            parse_submit_response(&html(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 34s left to wait. <a href=\"/2025/day/1\">[Return to Day 1]</a>"
+            )),
+            SubmitResult::TooRecent {
+                wait: Some(Duration::from_secs(94))
+            }
+        );
+        assert_eq!(
+            parse_submit_response(&html(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            SubmitResult::WrongLevel
+        );
+        assert_eq!(
+            parse_submit_response("Something &amp; else"),
+            SubmitResult::Unknown("Something & else".into())
+        );
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Find <em>the</em> <code>x &lt; y</code>:</p><pre><code>1 <em>2</em>
+3 4
+</code></pre><ul><li>one</li><li>two</li></ul></article><p>Your puzzle answer was <code>3</code>.</p></main>"#;
+
+        assert_eq!(
+            puzzle_to_markdown(html),
+            "## --- Day 1: Test ---\n\nFind *the* `x < y`:\n\n```\n1 2\n3 4\n```\n\n- one\n- two\n"
+        );
+    }
+
+    #[test]
+    fn downloads_from_base_url() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut head = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push(line.trim().to_string());
+            }
+
+            let body = "1 2\n";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            let _ = reader.read_to_end(&mut vec![]);
+            head
+        });
+
+        let client = Client::new(&format!("http://{address}/"), "secret");
+        assert_eq!(client.input(day!(1).with_year(2025)).unwrap(), "1 2\n");
+
+        let head = server.join().unwrap();
+        assert_eq!(head[0], "GET /2025/day/1/input HTTP/1.1");
+        assert!(
+            head.iter()
+                .any(|h| h.eq_ignore_ascii_case("cookie: session=secret"))
+        );
+    }
+}
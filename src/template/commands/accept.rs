@@ -0,0 +1,58 @@
+use std::process;
+
+use crate::template::{Day, Part, answers, inputs, protocol::Phase, registry, runner::RunOptions};
+
+/// Run a day against its real inputs and store the output as the known answers.
+/// If `part` is given, only the answer of that part is stored.
+/// If `input` is given, only that input is run.
+pub fn handle(day: Day, part: Option<Part>, input: Option<String>) {
+    if !registry::is_enabled() {
+        eprintln!("`accept` needs the `registry` feature. Please run it via `cargo accept`.");
+        process::exit(1);
+    }
+
+    let Some(solution) = registry::get(day) else {
+        eprintln!("Day {day} has no solution yet.");
+        process::exit(1);
+    };
+
+    let options = RunOptions {
+        input,
+        ..RunOptions::default()
+    };
+
+    let runs = inputs::run_each(solution, &options);
+
+    if runs.is_empty() {
+        process::exit(1);
+    }
+
+    println!("---");
+
+    for run in runs {
+        let input = run.input.name.as_deref();
+        let suffix = input.map_or(String::new(), |name| format!(" ({name})"));
+
+        for record in run.records {
+            let Phase::Part(record_part) = record.part else {
+                continue;
+            };
+
+            if part.is_some_and(|p| p != record_part) {
+                continue;
+            }
+
+            let Some(answer) = record.answer else {
+                println!("Part {record_part}{suffix} has no answer, skipping.");
+                continue;
+            };
+
+            if let Err(e) = answers::accept(day, input, record_part, &answer) {
+                eprintln!("Failed to store answer: {e}");
+                process::exit(1);
+            }
+
+            println!("🎄 Stored answer for part {record_part}{suffix}.");
+        }
+    }
+}
@@ -1,5 +1,34 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use std::time::Duration;
+
+use crate::template::{DaySelector, registry, run_multi::run_multi, runner::RunOptions};
+
+pub fn handle(selector: &DaySelector, timeout: Option<Duration>) {
+    if !registry::is_enabled() {
+        eprintln!("`all` needs the `registry` feature. Please run it via `cargo all`.");
+        process::exit(1);
+    }
+
+    let options = RunOptions {
+        timeout,
+        ..RunOptions::default()
+    };
+
+    let days = selector.days();
+    if days.is_empty() {
+        eprintln!("No days match `{selector}`.");
+        process::exit(1);
+    }
+
+    let run = run_multi(&days.into_iter().collect(), &options);
+
+    if !run.mismatches.is_empty() {
+        eprintln!();
+        eprintln!("Output differs from the known answer for:");
+        for mismatch in &run.mismatches {
+            eprintln!(" - {mismatch}");
+        }
+        process::exit(1);
+    }
 }
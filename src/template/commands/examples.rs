@@ -0,0 +1,106 @@
+use std::fs;
+
+use crate::template::{
+    Day, Part, aoc_cli, data_dir,
+    examples::{self, Prefill},
+};
+
+/// Writes an example block of `data/{year}/puzzles/{day}.md` to `data/{year}/examples/{day}.txt`,
+/// or to `data/{year}/examples/{day}-{part}.txt` if `part` is set.
+///
+/// An example file that is not empty is kept, unless `overwrite` is set.
+///
+/// Afterwards, the example answers found in the description are written into the tests of
+/// `src/bin/{year}-{day}.rs` that still assert `None`.
+pub fn handle(
+    day: Day,
+    block: Option<usize>,
+    part: Option<Part>,
+    overwrite: bool,
+) -> Result<(), String> {
+    let dir = data_dir(day.year());
+    let puzzle_path = aoc_cli::get_puzzle_path(day);
+    let example_path = match part {
+        Some(part) => format!("{dir}/examples/{day}-{part}.txt"),
+        None => format!("{dir}/examples/{day}.txt"),
+    };
+
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        return Err(format!(
+            "Could not read \"{puzzle_path}\". Use `cargo download {day}` to download it."
+        ));
+    };
+
+    let blocks = examples::extract(&puzzle);
+
+    let Some(selected) = examples::select(&blocks, block, part) else {
+        return Err(match block {
+            Some(index) => format!("Block {index} not found, found {} blocks.", blocks.len()),
+            None => format!("No example blocks found in \"{puzzle_path}\"."),
+        });
+    };
+
+    if blocks.len() > 1 {
+        println!("Found {} blocks:", blocks.len());
+        for (i, b) in blocks.iter().enumerate() {
+            let marker = if std::ptr::eq(b, selected) {
+                "→"
+            } else {
+                " "
+            };
+            println!("{marker} {}. (part {}) {}", i + 1, b.part, b.preview());
+        }
+        println!("Use `--block <n>` to pick another block.");
+        println!("---");
+    }
+
+    // NOTE: scaffolding creates an empty example file, which is filled in without `--overwrite`.
+    let exists = fs::read_to_string(&example_path).is_ok_and(|content| !content.is_empty());
+
+    if exists && !overwrite {
+        println!(
+            "Found existing example file \"{example_path}\", keeping it. Append `--overwrite` to replace it."
+        );
+    } else {
+        fs::write(&example_path, &selected.content)
+            .map_err(|e| format!("Failed to write example file: {e}"))?;
+        println!("🎄 Successfully wrote example to \"{example_path}\".");
+    }
+
+    for p in Part::ALL {
+        if part.is_none_or(|part| part == p) {
+            prefill_answer(day, &puzzle, p);
+        }
+    }
+
+    Ok(())
+}
+
+fn prefill_answer(day: Day, puzzle: &str, part: Part) {
+    let module_path = format!("src/bin/{}.rs", day.bin_name());
+    let test_name = match part {
+        Part::One => "test_part_one",
+        Part::Two => "test_part_two",
+    };
+
+    // NOTE: the description of part two is only available once part one is solved.
+    let Some(answer) = examples::find_answer(puzzle, part) else {
+        return;
+    };
+
+    let Ok(module) = fs::read_to_string(&module_path) else {
+        println!("Example answer of part {part}: {answer}");
+        return;
+    };
+
+    match examples::prefill(&module, part, &answer) {
+        Prefill::Written(module) => match fs::write(&module_path, module) {
+            Ok(()) => println!("🎄 Wrote example answer {answer} into `{test_name}`."),
+            Err(e) => eprintln!("Failed to write module file: {e}"),
+        },
+        Prefill::Edited => println!(
+            "Example answer of part {part}: {answer} (`{test_name}` was already edited, left untouched)"
+        ),
+        Prefill::Missing => println!("Example answer of part {part}: {answer}"),
+    }
+}
@@ -1,6 +1,12 @@
+pub mod accept;
 pub mod all;
 pub mod download;
+pub mod examples;
+pub mod progress;
 pub mod read;
+pub mod reset;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
@@ -0,0 +1,44 @@
+use std::process;
+
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET,
+    progress::{self, Progress},
+};
+
+pub fn handle(year: u16, sync: bool) {
+    let progress = if sync {
+        match progress::sync(year) {
+            Ok(progress) => progress,
+            Err(e) => {
+                eprintln!("failed to sync progress: {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        match Progress::read(year) {
+            Ok(progress) => progress,
+            Err(e) => {
+                eprintln!("failed to read progress: {e}");
+                process::exit(1);
+            }
+        }
+    };
+
+    if progress.stars.is_empty() {
+        println!("No stars yet.");
+    }
+
+    for (day, &stars) in &progress.stars {
+        let earned = "★".repeat(stars.into());
+        let missing = "☆".repeat(2 - usize::from(stars.min(2)));
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET} {earned}{missing}");
+    }
+
+    println!("---");
+    println!("🎄 {} stars.", progress.total());
+
+    match progress::update_readme(&progress) {
+        Ok(()) => println!("Stored updated stars table."),
+        Err(_) => eprintln!("Failed to store updated stars table."),
+    }
+}
@@ -0,0 +1,48 @@
+use std::process;
+
+use crate::template::{
+    Day,
+    commands::scaffold::{render_module, write},
+    now_unix,
+    scaffold::{self, Overwrite},
+};
+
+/// Backs up the module, input and example file of `day` to `data/{year}/backups/{day}-{timestamp}`
+/// and re-creates them, the module from the template called `template`.
+/// With `dry_run`, nothing is copied or written.
+pub fn handle(day: Day, template: Option<&str>, dry_run: bool) {
+    let module = render_module(day, template).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+    let targets = scaffold::plan(day, module, Overwrite::All);
+    let backup_dir = scaffold::backup_dir(day, now_unix());
+
+    if dry_run {
+        for target in targets.iter().filter(|target| target.exists) {
+            println!("Would back up \"{}\" to \"{backup_dir}\"", target.path);
+        }
+    } else {
+        match scaffold::backup(&targets, &backup_dir) {
+            Ok(copies) => {
+                for copy in copies {
+                    println!("Backed up to \"{copy}\"");
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to back up day {day}, nothing was re-created: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    if let Err(e) = write(day, &targets, dry_run) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    if !dry_run {
+        println!("---");
+        println!("🎄 Day {day} was reset.");
+    }
+}
@@ -1,79 +1,115 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+use std::fs;
+
+use crate::template::{
+    DEFAULT_YEAR, Day, data_dir,
+    scaffold::{self, Kind, Overwrite, Target},
+    templates::{self, DEFAULT_TEMPLATE},
 };
 
-use crate::template::Day;
+/// Creates the module, input and example file of `day`, the module from the template called
+/// `template`, see [`templates`].
+///
+/// Files that already exist are reported and kept, except for the module file if `overwrite` is set.
+/// With `dry_run`, nothing is written.
+pub fn handle(
+    day: Day,
+    overwrite: bool,
+    template: Option<&str>,
+    dry_run: bool,
+) -> Result<(), String> {
+    let overwrite = if overwrite {
+        Overwrite::Module
+    } else {
+        Overwrite::None
+    };
+    let targets = scaffold::plan(day, render_module(day, template)?, overwrite);
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+    for target in targets.iter().filter(|target| target.exists) {
+        if target.overwrite {
+            println!(
+                "Found existing {} \"{}\", overwriting it.",
+                target.kind, target.path
+            );
+        } else {
+            println!(
+                "Found existing {} \"{}\", keeping it.",
+                target.kind, target.path
+            );
+        }
+    }
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
+    if !targets.iter().any(Target::is_written) {
+        println!("---");
+        println!(
+            "Nothing to scaffold. Append `--overwrite` to re-create the module file, \
+            or use `cargo reset {day}{}` to back up and re-create all files of the day.",
+            year_flag(day)
+        );
+        return Ok(());
     }
-    file.truncate(true).write(true).open(path)
+
+    write(day, &targets, dry_run)?;
+
+    if dry_run {
+        return Ok(());
+    }
+
+    println!("---");
+    println!(
+        "🎄 Type `cargo solve {day}{}` to run your solution.",
+        year_flag(day)
+    );
+    Ok(())
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// The `--year` argument that selects the year of `day`, empty for the default year.
+fn year_flag(day: Day) -> String {
+    if day.year() == DEFAULT_YEAR {
+        String::new()
+    } else {
+        format!(" --year {}", day.year())
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Renders the module of `day` from the template called `template`, or the default template.
+pub fn render_module(day: Day, template: Option<&str>) -> Result<String, String> {
+    templates::load(template.unwrap_or(DEFAULT_TEMPLATE))
+        .map(|template| templates::render(&template, &templates::placeholders(day)))
+        .map_err(|e| format!("Failed to load template: {e}"))
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+/// Writes the targets that are missing or replaced and prints each of them,
+/// or only prints what would be written if `dry_run` is set.
+pub fn write(day: Day, targets: &[Target], dry_run: bool) -> Result<(), String> {
+    if !dry_run {
+        let dir = data_dir(day.year());
+        for folder in ["inputs", "examples", "puzzles"] {
+            fs::create_dir_all(format!("{dir}/{folder}"))
+                .map_err(|e| format!("Failed to create data directory: {e}"))?;
         }
-    };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+        scaffold::write(targets).map_err(|e| format!("Failed to write files: {e}"))?;
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    for target in targets.iter().filter(|target| target.is_written()) {
+        let action = match (target.exists, dry_run) {
+            (false, false) => "Created",
+            (false, true) => "Would create",
+            (true, false) => "Re-created",
+            (true, true) => "Would re-create",
+        };
+        let empty = if target.kind == Kind::Module {
+            ""
+        } else {
+            "empty "
+        };
+        println!("{action} {empty}{} \"{}\"", target.kind, target.path);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    if dry_run {
+        println!("---");
+        println!("Dry run, no files were written.");
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    Ok(())
 }
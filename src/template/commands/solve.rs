@@ -1,9 +1,20 @@
-use std::process::{Command, Stdio};
+use std::io::{BufRead, BufReader};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::inputs::DEFAULT_INPUT;
+use crate::template::protocol::{PartRecord, Phase};
+use crate::template::runner::{self, RunOptions};
+use crate::template::{Day, Part};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    allocs: bool,
+    submit_part: Option<Part>,
+    options: &RunOptions,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -16,19 +27,80 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if allocs {
+        cmd_args.extend(["--features".to_string(), "count-allocs".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    let Some(part) = submit_part else {
+        cmd_args.extend(options.to_args());
+
+        let mut cmd = Command::new("cargo")
+            .args(&cmd_args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .unwrap();
+
+        cmd.wait().unwrap();
+        return;
+    };
+
+    // NOTE: only the default input belongs to the account that answers are submitted for.
+    if options
+        .input
+        .as_deref()
+        .is_some_and(|name| name != DEFAULT_INPUT)
+    {
+        eprintln!("Only answers of the default input can be submitted.");
+        process::exit(1);
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
+    let options = RunOptions {
+        json: true,
+        input: Some(DEFAULT_INPUT.to_string()),
+        ..options.clone()
+    };
+    cmd_args.extend(options.to_args());
+
+    let Some(answer) = run_for_answer(&cmd_args, part) else {
+        eprintln!("Part {part} has no answer to submit.");
+        process::exit(1);
+    };
+
+    runner::submit_answer(day, part, &answer);
+}
+
+/// Runs the solution binary with `--json`, prints its records the way the binary would have,
+/// and returns the answer of `part`.
+fn run_for_answer(cmd_args: &[String], part: Part) -> Option<String> {
+    let mut child = Command::new("cargo")
+        .args(cmd_args)
+        .stdout(Stdio::piped())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let stdout = child.stdout.take().unwrap();
+    let mut answer = None;
+
+    for line in BufReader::new(stdout).lines() {
+        let line = line.unwrap();
+
+        let Some(record) = PartRecord::from_json_line(&line) else {
+            // NOTE: pass through anything the solution prints on its own.
+            println!("{line}");
+            continue;
+        };
+
+        runner::print_record(&record);
+
+        if record.part == Phase::Part(part) {
+            answer = record.answer;
+        }
+    }
+
+    child.wait().unwrap();
+    answer
 }
@@ -1,40 +1,113 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
+use crate::template::compare;
+use crate::template::history;
+use crate::template::memory;
 use crate::template::run_multi::run_multi;
+use crate::template::runner::{BenchConfig, RunOptions};
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Day, DaySelector, all_days, now_unix, readme_benchmarks, registry};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub use crate::template::compare::DEFAULT_THRESHOLD;
 
-    let days_to_run = day.map_or_else(
+pub fn handle(
+    year: u16,
+    selector: Option<DaySelector>,
+    run_all: bool,
+    store: bool,
+    compare_threshold: Option<f64>,
+    timeout: Option<Duration>,
+    bench: BenchConfig,
+) {
+    if !registry::is_enabled() {
+        eprintln!("`time` needs the `registry` feature. Please run it via `cargo time`.");
+        process::exit(1);
+    }
+
+    let stored_timings = match Timings::read_from_file(year) {
+        Ok(timings) => timings,
+        Err(e) => {
+            eprintln!("Failed to read stored timings: {e}");
+            process::exit(1);
+        }
+    };
+
+    let days_to_run: HashSet<_> = selector.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                all_days(year).collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, bench every day that has a stored baseline.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |selector| selector.days().into_iter().collect(),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        time: true,
+        timeout,
+        bench,
+        ..RunOptions::default()
+    };
+
+    let timings = run_multi(&days_to_run, &options).timings.unwrap();
+
+    let regressions = compare_threshold.map_or(0, |threshold| {
+        compare::print(&compare::compare(&stored_timings, &timings), threshold)
+    });
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let merged_timings = if memory::is_enabled() {
+            println!();
+            println!(
+                "Allocations were counted, which slows down every allocation. Storing the memory of each part only, run `cargo time` without `count-allocs` to store timings."
+            );
+            stored_timings.merge_memory(&timings)
+        } else {
+            if let Err(e) = history::append(&timings, year, now_unix()) {
+                eprintln!("Failed to append to the timing history: {e}");
+            }
+            stored_timings.merge(&timings)
+        };
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, year) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
+
+    if regressions > 0 {
+        eprintln!();
+        eprintln!("{regressions} part(s) regressed beyond the threshold.");
+        process::exit(1);
+    }
+}
+
+/// Prints the stored timings of `day` over time, see `time --store`.
+pub fn handle_history(day: Day) {
+    let entries = history::read(day);
+
+    if entries.is_empty() {
+        eprintln!(
+            "No timing history for day {day} of {}. Use `cargo time {day} --store` to record one.",
+            day.year()
+        );
+        process::exit(1);
+    }
+
+    history::print(day, &entries);
 }
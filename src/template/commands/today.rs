@@ -0,0 +1,105 @@
+use std::process;
+
+use crate::template::{
+    Day,
+    aoc_client::{self, Backend, Client},
+    commands::{download, read, scaffold},
+    days_in_year,
+    unlock::{self, Clock},
+};
+
+/// Attempts to download the input after an unlock, the server may need a moment to serve it.
+const DOWNLOAD_ATTEMPTS: u32 = 6;
+
+pub fn handle(clock: &impl Clock, wait: bool) {
+    // NOTE: fail before the countdown rather than after it if the session is missing.
+    if wait
+        && Backend::from_env() == Backend::Native
+        && let Err(e) = Client::from_env()
+    {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    let day = match select_day(clock, wait) {
+        Ok(day) => day,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1)
+        }
+    };
+
+    let downloaded = scaffold::handle(day, false, None, false).and_then(|()| {
+        if wait {
+            unlock::retry(clock, DOWNLOAD_ATTEMPTS, || aoc_client::download(day))
+                .map_err(|e| format!("failed to download day {day}: {e}"))
+        } else {
+            download::handle(day)
+        }
+    });
+
+    if let Err(e) = downloaded {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    read::handle(day);
+}
+
+/// The puzzle to set up: today's puzzle, or with `wait` the next puzzle once it is unlocked.
+fn select_day(clock: &impl Clock, wait: bool) -> Result<Day, String> {
+    if wait {
+        let day = unlock::next_unlock(clock.now())
+            .ok_or("could not determine the next puzzle to unlock.")?;
+        unlock::wait_for(clock, day);
+        return Ok(day);
+    }
+
+    Day::today_at(clock.now()).ok_or_else(|| {
+        let year = Day::current_year();
+        format!(
+            "`today` command can only be run between the 1st and the {}th of \
+            december {year}. Please use `scaffold` with a specific day, or append \
+            `--wait` to wait for the next puzzle.",
+            days_in_year(year)
+        )
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::select_day;
+    use crate::template::{
+        Day,
+        unlock::{Clock, MockClock, utc},
+    };
+
+    fn day(year: u16, day: u8) -> Day {
+        Day::of_year(year, day).unwrap()
+    }
+
+    #[test]
+    fn selects_todays_puzzle() {
+        let clock = MockClock::at(utc(2024, 12, 3, 22, 0));
+        assert_eq!(select_day(&clock, false), Ok(day(2024, 3)));
+        assert_eq!(clock.sleeps.get(), 0);
+
+        assert!(select_day(&MockClock::at(utc(2024, 11, 30, 22, 0)), false).is_err());
+    }
+
+    #[test]
+    fn waits_for_next_puzzle_during_advent() {
+        let clock = MockClock::at(utc(2024, 12, 3, 22, 0));
+        assert_eq!(select_day(&clock, true), Ok(day(2024, 4)));
+        assert_eq!(clock.now(), utc(2024, 12, 4, 5, 0));
+    }
+
+    #[test]
+    fn waits_for_first_puzzle_before_advent() {
+        let clock = MockClock::at(utc(2024, 11, 30, 22, 0));
+        assert_eq!(select_day(&clock, true), Ok(day(2024, 1)));
+        assert_eq!(clock.now(), utc(2024, 12, 1, 5, 0));
+    }
+}
@@ -0,0 +1,181 @@
+/// Compares fresh benchmarks against the timings stored in `data/timings.json`.
+use std::time::Duration;
+
+use crate::template::{
+    Day,
+    runner::BenchStats,
+    timings::{Timing, Timings},
+};
+
+/// Regression threshold in percent, used when `--threshold` is not passed.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Change of a single part's mean execution time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    /// `"Parse"`, `"Part 1"` or `"Part 2"`.
+    pub label: &'static str,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl PartDelta {
+    /// Change relative to the baseline, in percent. Positive values mean the part got slower.
+    pub fn percent_change(&self) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        let (baseline, current) = (
+            self.baseline.as_nanos() as f64,
+            self.current.as_nanos() as f64,
+        );
+
+        if baseline == 0.0 {
+            return 0.0;
+        }
+
+        (current - baseline) / baseline * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent_change() > threshold
+    }
+
+    fn format_delta(&self) -> String {
+        if self.current >= self.baseline {
+            format!("+{:.1?}", self.current - self.baseline)
+        } else {
+            format!("-{:.1?}", self.baseline - self.current)
+        }
+    }
+}
+
+fn stats_of(timing: &Timing) -> [(&'static str, Option<BenchStats>); 3] {
+    [
+        ("Parse", timing.parse_stats),
+        ("Part 1", timing.part_1_stats),
+        ("Part 2", timing.part_2_stats),
+    ]
+}
+
+/// Pair up every part that was benched both in `baseline` and in `current`.
+/// Parts stored without stats (older timing files) cannot be compared and are skipped.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<PartDelta> {
+    let mut deltas = vec![];
+
+    for timing in &current.data {
+        let Some(stored) = baseline.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for ((label, old), (_, new)) in stats_of(stored).into_iter().zip(stats_of(timing)) {
+            if let (Some(old), Some(new)) = (old, new) {
+                deltas.push(PartDelta {
+                    day: timing.day,
+                    label,
+                    baseline: old.mean,
+                    current: new.mean,
+                });
+            }
+        }
+    }
+
+    deltas
+}
+
+/// Print the comparison and return the number of parts that regressed beyond `threshold`.
+pub fn print(deltas: &[PartDelta], threshold: f64) -> usize {
+    println!();
+    println!("Compared to stored timings (threshold +{threshold:.1}%):");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare against.");
+        return 0;
+    }
+
+    let mut regressions = 0;
+
+    for delta in deltas {
+        let marker = if delta.is_regression(threshold) {
+            regressions += 1;
+            " ✘ regression"
+        } else {
+            ""
+        };
+
+        println!(
+            "Day {} {}: {:.1?} → {:.1?} ({}, {:+.1}%){marker}",
+            delta.day,
+            delta.label,
+            delta.baseline,
+            delta.current,
+            delta.format_delta(),
+            delta.percent_change()
+        );
+    }
+
+    regressions
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{PartDelta, compare};
+    use crate::{
+        day,
+        template::{
+            runner::BenchStats,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn timing(part_1_nanos: Option<u64>) -> Timings {
+        let stats = part_1_nanos.map(|n| BenchStats::from_samples(&[Duration::from_nanos(n)]));
+
+        Timings {
+            data: vec![Timing {
+                part_1_stats: stats,
+                ..Timing::new(day!(1))
+            }],
+        }
+    }
+
+    #[test]
+    fn compares_benched_parts() {
+        let deltas = compare(&timing(Some(100)), &timing(Some(150)));
+        assert_eq!(
+            deltas,
+            vec![PartDelta {
+                day: day!(1),
+                label: "Part 1",
+                baseline: Duration::from_nanos(100),
+                current: Duration::from_nanos(150),
+            }]
+        );
+        assert_eq!(deltas[0].percent_change(), 50.0);
+        assert_eq!(deltas[0].is_regression(10.0), true);
+        assert_eq!(deltas[0].is_regression(50.0), false);
+    }
+
+    #[test]
+    fn skips_parts_without_stats() {
+        assert_eq!(compare(&timing(None), &timing(Some(150))), vec![]);
+        assert_eq!(compare(&Timings::default(), &timing(Some(150))), vec![]);
+    }
+
+    #[test]
+    fn formats_improvements() {
+        let delta = PartDelta {
+            day: day!(1),
+            label: "Part 2",
+            baseline: Duration::from_millis(2),
+            current: Duration::from_millis(1),
+        };
+        assert_eq!(delta.percent_change(), -50.0);
+        assert_eq!(delta.format_delta(), "-1.0ms");
+        assert_eq!(delta.is_regression(0.0), false);
+    }
+}
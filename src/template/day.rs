@@ -3,15 +3,32 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Year of days that were created without an explicit year, i.e. `AOC_YEAR` at compile time.
+pub const DEFAULT_YEAR: u16 = match parse_year(
+    env!(
+        "AOC_YEAR",
+        "`AOC_YEAR` is not set, please set it in `.cargo/config.toml`."
+    )
+    .as_bytes(),
+) {
+    Some(year) => year,
+    None => panic!("`AOC_YEAR` is not a valid year."),
+};
+
+/// Number of puzzles in the advent calendar of `year`. Since 2025, the calendar has 12 days.
+pub const fn days_in_year(year: u16) -> u8 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
+/// A valid day number of advent (i.e. an integer in range 1 to [`days_in_year`]) of a puzzle year.
 ///
 /// # Display
-/// This value displays as a two digit number.
+/// This value displays as a two digit number, without the year.
 ///
 /// ```
 /// # use advent_of_code::Day;
@@ -19,53 +36,154 @@ const SERVER_UTC_OFFSET: i32 = -5;
 /// assert_eq!(day.to_string(), "08")
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Day(u8);
+pub struct Day {
+    year: u16,
+    day: u8,
+}
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
+    /// Creates a [`Day`] of [`DEFAULT_YEAR`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub const fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        Self::of_year(DEFAULT_YEAR, day)
+    }
+
+    /// Creates a [`Day`] of `year` from the provided value if it's in the calendar of that year,
+    /// returns [`None`] otherwise.
+    pub const fn of_year(year: u16, day: u8) -> Option<Self> {
+        if day == 0 || day > days_in_year(year) {
             return None;
         }
-        Some(Self(day))
+        Some(Self { year, day })
+    }
+
+    /// Parses a day number of `year`, e.g. from command-line arguments.
+    pub fn parse(s: &str, year: u16) -> Result<Self, DayFromStrError> {
+        s.parse()
+            .ok()
+            .and_then(|day| Self::of_year(year, day))
+            .ok_or(DayFromStrError { year })
+    }
+
+    /// Returns the same day number in `year`, for tests.
+    /// The day is not checked against the calendar of `year`, use [`Day::of_year`] for that.
+    #[cfg(feature = "test_lib")]
+    #[must_use]
+    pub const fn with_year(self, year: u16) -> Self {
+        Self { year, ..self }
     }
 
     /// Converts the [`Day`] into an [`u8`].
     pub fn into_inner(self) -> u8 {
-        self.0
+        self.day
+    }
+
+    /// The puzzle year of the [`Day`].
+    pub const fn year(self) -> u16 {
+        self.year
+    }
+
+    /// Name of the binary of this day in `src/bin`, e.g. `2025-01`.
+    pub fn bin_name(self) -> String {
+        format!("{}-{self}", self.year)
+    }
+
+    /// Returns the year of a solution from the path of its file, e.g. `src/bin/2025-01.rs`.
+    /// Files without a year prefix belong to [`DEFAULT_YEAR`].
+    #[doc(hidden)]
+    pub const fn year_of_file(path: &str) -> u16 {
+        let bytes = path.as_bytes();
+
+        // NOTE: find the start of the file name, the path may use either kind of separator.
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        let (_, name) = bytes.split_at(start);
+        if name.len() < 5 || name[4] != b'-' {
+            return DEFAULT_YEAR;
+        }
+
+        match parse_year(name.split_at(4).0) {
+            Some(year) => year,
+            None => DEFAULT_YEAR,
+        }
+    }
+}
+
+/// Parses a four digit year in a const context.
+const fn parse_year(bytes: &[u8]) -> Option<u16> {
+    if bytes.len() != 4 {
+        return None;
+    }
+
+    let mut year: u16 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        year = year * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
     }
+
+    Some(year)
 }
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's part of this year's advent calendar, `None` otherwise.
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        Self::today_at(Utc::now())
+    }
+
+    /// Returns the day of advent at the moment `now`, `None` if there is no puzzle on that date.
+    pub fn today_at(now: DateTime<Utc>) -> Option<Self> {
+        let today = now.with_timezone(&server_offset());
+        if today.month() == 12 {
+            let year = u16::try_from(today.year()).ok()?;
+            Self::of_year(year, u8::try_from(today.day()).ok()?)
         } else {
             None
         }
     }
+
+    /// Returns the current year in the timezone of the puzzle server.
+    pub fn current_year() -> u16 {
+        u16::try_from(Utc::now().with_timezone(&server_offset()).year()).unwrap_or(DEFAULT_YEAR)
+    }
+
+    /// Returns the moment the puzzle of this day unlocks, i.e. midnight in the timezone of the puzzle server.
+    pub fn unlocks_at(self) -> DateTime<Utc> {
+        server_offset()
+            .with_ymd_and_hms(i32::from(self.year), 12, u32::from(self.day), 0, 0, 0)
+            .single()
+            .expect("every day of advent is a valid date")
+            .with_timezone(&Utc)
+    }
+}
+
+#[cfg(feature = "today")]
+fn server_offset() -> FixedOffset {
+    FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).expect("the server offset is a valid offset")
 }
 
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:02}", self.0)
+        write!(f, "{:02}", self.day)
     }
 }
 
 impl PartialEq<u8> for Day {
     fn eq(&self, other: &u8) -> bool {
-        self.0.eq(other)
+        self.day.eq(other)
     }
 }
 
 impl PartialOrd<u8> for Day {
     fn partial_cmp(&self, other: &u8) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(other)
+        self.day.partial_cmp(other)
     }
 }
 
@@ -75,39 +193,45 @@ impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
+        Self::parse(s, DEFAULT_YEAR)
     }
 }
 
 /// An error which can be returned when parsing a [`Day`].
 #[derive(Debug)]
-pub struct DayFromStrError;
+pub struct DayFromStrError {
+    year: u16,
+}
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(
+            f,
+            "expecting a day number between 1 and {} for {}",
+            days_in_year(self.year),
+            self.year
+        )
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of the advent calendar of `year`.
+pub fn all_days(year: u16) -> AllDays {
+    AllDays::new(year)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the advent calendar of a year.
 pub struct AllDays {
+    year: u16,
     current: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(year: u16) -> Self {
+        Self { year, current: 1 }
     }
 }
 
@@ -115,11 +239,7 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
-            return None;
-        }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
-        let day = Day(self.current);
+        let day = Day::of_year(self.year, self.current)?;
         self.current += 1;
 
         Some(day)
@@ -134,7 +254,7 @@ macro_rules! day {
     ($day:expr) => {
         const {
             $crate::template::Day::new($day)
-                .expect("invalid day number, expecting a value between 1 and 25")
+                .expect("invalid day number, expecting a day of the calendar of `AOC_YEAR`")
         }
     };
 }
@@ -143,39 +263,82 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{Day, all_days};
+    use super::{DEFAULT_YEAR, Day, all_days, days_in_year};
+
+    fn day(day: u8) -> Day {
+        Day { year: 2024, day }
+    }
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
-
-        assert_eq!(iter.next(), Some(Day(1)));
-        assert_eq!(iter.next(), Some(Day(2)));
-        assert_eq!(iter.next(), Some(Day(3)));
-        assert_eq!(iter.next(), Some(Day(4)));
-        assert_eq!(iter.next(), Some(Day(5)));
-        assert_eq!(iter.next(), Some(Day(6)));
-        assert_eq!(iter.next(), Some(Day(7)));
-        assert_eq!(iter.next(), Some(Day(8)));
-        assert_eq!(iter.next(), Some(Day(9)));
-        assert_eq!(iter.next(), Some(Day(10)));
-        assert_eq!(iter.next(), Some(Day(11)));
-        assert_eq!(iter.next(), Some(Day(12)));
-        assert_eq!(iter.next(), Some(Day(13)));
-        assert_eq!(iter.next(), Some(Day(14)));
-        assert_eq!(iter.next(), Some(Day(15)));
-        assert_eq!(iter.next(), Some(Day(16)));
-        assert_eq!(iter.next(), Some(Day(17)));
-        assert_eq!(iter.next(), Some(Day(18)));
-        assert_eq!(iter.next(), Some(Day(19)));
-        assert_eq!(iter.next(), Some(Day(20)));
-        assert_eq!(iter.next(), Some(Day(21)));
-        assert_eq!(iter.next(), Some(Day(22)));
-        assert_eq!(iter.next(), Some(Day(23)));
-        assert_eq!(iter.next(), Some(Day(24)));
-        assert_eq!(iter.next(), Some(Day(25)));
+        let mut iter = all_days(2024);
+
+        assert_eq!(iter.next(), Some(day(1)));
+        assert_eq!(iter.next(), Some(day(2)));
+        assert_eq!(iter.next(), Some(day(3)));
+        assert_eq!(iter.next(), Some(day(4)));
+        assert_eq!(iter.next(), Some(day(5)));
+        assert_eq!(iter.next(), Some(day(6)));
+        assert_eq!(iter.next(), Some(day(7)));
+        assert_eq!(iter.next(), Some(day(8)));
+        assert_eq!(iter.next(), Some(day(9)));
+        assert_eq!(iter.next(), Some(day(10)));
+        assert_eq!(iter.next(), Some(day(11)));
+        assert_eq!(iter.next(), Some(day(12)));
+        assert_eq!(iter.next(), Some(day(13)));
+        assert_eq!(iter.next(), Some(day(14)));
+        assert_eq!(iter.next(), Some(day(15)));
+        assert_eq!(iter.next(), Some(day(16)));
+        assert_eq!(iter.next(), Some(day(17)));
+        assert_eq!(iter.next(), Some(day(18)));
+        assert_eq!(iter.next(), Some(day(19)));
+        assert_eq!(iter.next(), Some(day(20)));
+        assert_eq!(iter.next(), Some(day(21)));
+        assert_eq!(iter.next(), Some(day(22)));
+        assert_eq!(iter.next(), Some(day(23)));
+        assert_eq!(iter.next(), Some(day(24)));
+        assert_eq!(iter.next(), Some(day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_of_short_calendar() {
+        let days: Vec<_> = all_days(2025).map(Day::into_inner).collect();
+        assert_eq!(days, (1..=12).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn days_of_calendar() {
+        assert_eq!(days_in_year(2015), 25);
+        assert_eq!(days_in_year(2024), 25);
+        assert_eq!(days_in_year(2025), 12);
+
+        assert_eq!(Day::of_year(2024, 20), Some(day(20)));
+        assert_eq!(Day::of_year(2025, 13), None);
+        assert_eq!(Day::of_year(2024, 0), None);
+
+        assert_eq!(Day::parse("20", 2024).unwrap(), day(20));
+        assert_eq!(
+            Day::parse("13", 2025).unwrap_err().to_string(),
+            "expecting a day number between 1 and 12 for 2025"
+        );
+        assert!(Day::parse("x", 2024).is_err());
+    }
+
+    #[test]
+    fn years_of_files() {
+        assert_eq!(Day::year_of_file("src/bin/2024-01.rs"), 2024);
+        assert_eq!(Day::year_of_file("C:\\aoc\\src\\bin\\2016-25.rs"), 2016);
+        assert_eq!(Day::year_of_file("/aoc/2024-src/bin/01.rs"), DEFAULT_YEAR);
+        assert_eq!(Day::year_of_file("src/bin/20x4-01.rs"), DEFAULT_YEAR);
+    }
+
+    #[test]
+    fn displays_without_year() {
+        assert_eq!(day(8).to_string(), "08");
+        assert_eq!(day(8).bin_name(), "2024-08");
+        assert_eq!(day(8).with_year(2015).year(), 2015);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
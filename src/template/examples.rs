@@ -0,0 +1,315 @@
+/// Extracts example blocks and example answers from downloaded puzzle descriptions
+/// in `data/{year}/puzzles/{day}.md`.
+use crate::template::{Part, aoc_client::html_to_text};
+
+const FENCE: &str = "```";
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// A code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+    /// The part whose description contains the block.
+    pub part: Part,
+    pub content: String,
+}
+
+impl Block {
+    /// First line of the block, used to tell blocks apart when listing them.
+    pub fn preview(&self) -> &str {
+        self.content.lines().next().unwrap_or_default()
+    }
+}
+
+/// Extracts all code blocks of a puzzle description in the order they appear.
+/// Both fenced markdown blocks and raw `<pre><code>` blocks are supported.
+pub fn extract(markdown: &str) -> Vec<Block> {
+    let markdown = fence_html_blocks(markdown);
+
+    let mut blocks = vec![];
+    let mut part = Part::One;
+    let mut current: Option<String> = None;
+
+    for line in markdown.lines() {
+        let is_fence = line.trim_start().starts_with(FENCE);
+
+        match current.as_mut() {
+            Some(content) if is_fence => {
+                blocks.push(Block {
+                    part,
+                    content: std::mem::take(content),
+                });
+                current = None;
+            }
+            Some(content) => {
+                content.push_str(line);
+                content.push('\n');
+            }
+            None if is_fence => current = Some(String::new()),
+            None if line.contains(PART_TWO_HEADING) => part = Part::Two,
+            None => {}
+        }
+    }
+
+    blocks
+}
+
+/// Picks the block at 1-based `index` if set. Otherwise picks the first block of `part`,
+/// falling back to the first block of the description.
+pub fn select(blocks: &[Block], index: Option<usize>, part: Option<Part>) -> Option<&Block> {
+    match index {
+        Some(index) => blocks.get(index.checked_sub(1)?),
+        None => part
+            .and_then(|part| blocks.iter().find(|block| block.part == part))
+            .or(blocks.first()),
+    }
+}
+
+/// Finds the answer of the example in the description of `part`, i.e. the last emphasized number
+/// of that part's description, e.g. `142` in `In this example, the answer is *142*.`
+pub fn find_answer(markdown: &str, part: Part) -> Option<String> {
+    let (part_one, part_two) = match markdown.find(PART_TWO_HEADING) {
+        Some(index) => markdown.split_at(index),
+        None => (markdown, ""),
+    };
+
+    let text = match part {
+        Part::One => part_one,
+        Part::Two => part_two,
+    };
+
+    // NOTE: code blocks are skipped as they may contain stars, e.g. in grids.
+    let prose = text.split(FENCE).step_by(2).collect::<Vec<_>>().join("\n");
+
+    // NOTE: odd segments are emphasized, the text between two emphases is not.
+    // The last segment follows the last star, it is never emphasized.
+    let segments: Vec<&str> = prose.split('*').collect();
+
+    segments[..segments.len() - 1]
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .map(|segment| segment.trim_matches('`'))
+        .rfind(|segment| is_number(segment))
+        .map(String::from)
+}
+
+fn is_number(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Outcome of writing an example answer into the test of a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Prefill {
+    /// The test asserted `None` and now asserts the answer. Contains the updated module.
+    Written(String),
+    /// The test was already edited and is left untouched.
+    Edited,
+    /// The module has no test for this part.
+    Missing,
+}
+
+/// Writes `answer` into the `test_part_one` / `test_part_two` function of a solution module,
+/// as long as the test still contains the `assert_eq!(result, None);` of the scaffold.
+pub fn prefill(module: &str, part: Part, answer: &str) -> Prefill {
+    const PLACEHOLDER: &str = "assert_eq!(result, None);";
+
+    let name = match part {
+        Part::One => "fn test_part_one()",
+        Part::Two => "fn test_part_two()",
+    };
+
+    let Some(start) = module.find(name) else {
+        return Prefill::Missing;
+    };
+
+    let body_start = start + name.len();
+    let body_end = module[body_start..]
+        .find("fn ")
+        .map_or(module.len(), |i| body_start + i);
+
+    match module[body_start..body_end].find(PLACEHOLDER) {
+        Some(i) => {
+            let at = body_start + i;
+            Prefill::Written(format!(
+                "{}assert_eq!(result, Some({answer}));{}",
+                &module[..at],
+                &module[at + PLACEHOLDER.len()..]
+            ))
+        }
+        None => Prefill::Edited,
+    }
+}
+
+/// Rewrites `<pre><code>...</code></pre>` to fenced blocks, stripping tags like `<em>`.
+fn fence_html_blocks(markdown: &str) -> String {
+    const START: &str = "<pre><code>";
+    const END: &str = "</code></pre>";
+
+    let mut result = String::new();
+    let mut rest = markdown;
+
+    while let Some(start) = rest.find(START) {
+        let Some(end) = rest[start..].find(END) else {
+            break;
+        };
+
+        let code = html_to_text(&rest[start + START.len()..start + end]);
+
+        result.push_str(&rest[..start]);
+        result.push('\n');
+        result.push_str(FENCE);
+        result.push('\n');
+        result.push_str(&code);
+        if !code.ends_with('\n') {
+            result.push('\n');
+        }
+        result.push_str(FENCE);
+        result.push('\n');
+
+        rest = &rest[start + end + END.len()..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Block, Prefill, extract, find_answer, prefill, select};
+    use crate::template::Part;
+
+    const PUZZLE: &str = "## --- Day 1: Test ---
+
+For example:
+
+```
+1 2
+3 4
+```
+
+Or `inline`:
+
+```
+5
+```
+
+## --- Part Two ---
+
+Now:
+
+<pre><code>a &lt; <em>b</em>
+</code></pre>
+";
+
+    const MODULE: &str = "#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, Some(7));
+    }
+}
+";
+
+    fn block(part: Part, content: &str) -> Block {
+        Block {
+            part,
+            content: content.into(),
+        }
+    }
+
+    #[test]
+    fn extracts_blocks() {
+        assert_eq!(
+            extract(PUZZLE),
+            vec![
+                block(Part::One, "1 2\n3 4\n"),
+                block(Part::One, "5\n"),
+                block(Part::Two, "a < b\n")
+            ]
+        );
+        assert_eq!(extract("## --- Day 1: Test ---\n\nNo examples.\n"), vec![]);
+    }
+
+    #[test]
+    fn selects_blocks() {
+        let blocks = extract(PUZZLE);
+        assert_eq!(select(&blocks, None, None), Some(&blocks[0]));
+        assert_eq!(select(&blocks, Some(2), None), Some(&blocks[1]));
+        assert_eq!(select(&blocks, Some(2), Some(Part::Two)), Some(&blocks[1]));
+        assert_eq!(select(&blocks, None, Some(Part::Two)), Some(&blocks[2]));
+        assert_eq!(
+            select(&blocks[..2], None, Some(Part::Two)),
+            Some(&blocks[0])
+        );
+        assert_eq!(select(&blocks, Some(0), None), None);
+        assert_eq!(select(&blocks, Some(4), None), None);
+    }
+
+    #[test]
+    fn finds_answers() {
+        let puzzle = "## --- Day 1: Test ---
+
+```
+1 *2*
+3 * 4
+```
+
+Adding `*these*` results in `*-12*`. Finally, *`142`* and * 5 *. What is the sum?
+
+## --- Part Two ---
+
+Now, the sum is *281*.
+
+Your puzzle answer was `54634`.
+";
+        assert_eq!(find_answer(puzzle, Part::One), Some("142".into()));
+        assert_eq!(find_answer(puzzle, Part::Two), Some("281".into()));
+        assert_eq!(
+            find_answer("## --- Day 1: Test ---\n\nA *b* c.", Part::One),
+            None
+        );
+        assert_eq!(
+            find_answer(puzzle.split("## --- Part Two").next().unwrap(), Part::Two),
+            None
+        );
+    }
+
+    #[test]
+    fn skips_numbers_between_emphases() {
+        assert_eq!(
+            find_answer(
+                "## --- Day 1: Test ---\n\nThe answer is *2*0*x*.",
+                Part::One
+            ),
+            Some("2".into())
+        );
+        assert_eq!(
+            find_answer("## --- Day 1: Test ---\n\nA *b*7*c* 9", Part::One),
+            None
+        );
+    }
+
+    #[test]
+    fn prefills_tests() {
+        let Prefill::Written(module) = prefill(MODULE, Part::One, "142") else {
+            panic!("expected the test of part one to be written");
+        };
+        assert!(module.contains("assert_eq!(result, Some(142));"));
+        assert!(module.contains("assert_eq!(result, Some(7));"));
+        assert_eq!(prefill(&module, Part::One, "142"), Prefill::Edited);
+        assert_eq!(prefill(MODULE, Part::Two, "281"), Prefill::Edited);
+        assert_eq!(prefill("fn main() {}", Part::One, "142"), Prefill::Missing);
+    }
+}
@@ -0,0 +1,457 @@
+/// Append-only history of stored timings in `data/{year}/timings_history.jsonl`, one JSON object per line.
+///
+/// Every entry records the environment the day was benched in, so a slowdown can be traced back
+/// to the commit, toolchain or machine that caused it.
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Error, Write};
+use std::process::Command;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    DEFAULT_YEAR, Day,
+    compare::{DEFAULT_THRESHOLD, PartDelta},
+    data_dir,
+    timings::{Timing, Timings},
+};
+
+const LABELS: [&str; 3] = ["Parse", "Part 1", "Part 2"];
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Where and with what a day was benched.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Environment {
+    /// Short hash of the checked out commit.
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    /// Output of `rustc --version` for the compiler of this build.
+    pub rustc: Option<String>,
+    /// Model name of the CPU, read from `/proc/cpuinfo`.
+    pub cpu: Option<String>,
+    /// Cargo profile of this build, e.g. `release`.
+    pub profile: String,
+    pub threads: usize,
+}
+
+impl Environment {
+    pub fn detect() -> Self {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+
+        let commit = git(&["rev-parse", "--short", "HEAD"]);
+        let dirty = commit.is_some()
+            && git(&["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|status| !status.is_empty());
+
+        Self {
+            commit,
+            dirty,
+            rustc: option_env!("AOC_RUSTC_VERSION")
+                .filter(|version| !version.is_empty())
+                .map(String::from),
+            cpu: fs::read_to_string("/proc/cpuinfo")
+                .ok()
+                .and_then(|cpuinfo| cpu_model(&cpuinfo)),
+            profile: option_env!("AOC_PROFILE").unwrap_or("unknown").into(),
+            threads: thread::available_parallelism().map_or(1, usize::from),
+        }
+    }
+}
+
+/// Finds the first `model name` of `/proc/cpuinfo`.
+fn cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "model name").then(|| value.trim().to_string())
+    })
+}
+
+/// Mean execution times of a day at one point in time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub day: Day,
+    pub environment: Environment,
+    pub parse: Option<Duration>,
+    pub part_1: Option<Duration>,
+    pub part_2: Option<Duration>,
+}
+
+impl Entry {
+    /// Creates an entry from the benched parts of `timing`, returns `None` if no part was benched.
+    pub fn from_timing(timing: &Timing, timestamp: u64, environment: &Environment) -> Option<Self> {
+        let entry = Self {
+            timestamp,
+            day: timing.day,
+            environment: environment.clone(),
+            parse: timing.parse_stats.map(|stats| stats.mean),
+            part_1: timing.part_1_stats.map(|stats| stats.mean),
+            part_2: timing.part_2_stats.map(|stats| stats.mean),
+        };
+
+        (entry.parse.is_some() || entry.part_1.is_some() || entry.part_2.is_some()).then_some(entry)
+    }
+
+    fn parts(&self) -> [Option<Duration>; 3] {
+        [self.parse, self.part_1, self.part_2]
+    }
+}
+
+/// Path of the history of a year, e.g. `data/2025/timings_history.jsonl`.
+fn path(year: u16) -> String {
+    format!("{}/timings_history.jsonl", data_dir(year))
+}
+
+/// Appends an entry for every benched day of `timings` to the history of `year`.
+pub fn append(timings: &Timings, year: u16, timestamp: u64) -> Result<(), Error> {
+    let environment = Environment::detect();
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path(year))?;
+
+    for entry in timings
+        .data
+        .iter()
+        .filter_map(|timing| Entry::from_timing(timing, timestamp, &environment))
+    {
+        let line = JsonValue::from(&entry)
+            .stringify()
+            .expect("history entry should be serializable");
+        writeln!(file, "{line}")?;
+    }
+
+    Ok(())
+}
+
+/// Reads the entries of `day`, oldest first. Lines that can't be parsed are skipped.
+pub fn read(day: Day) -> Vec<Entry> {
+    let Ok(history) = fs::read_to_string(path(day.year())) else {
+        return vec![];
+    };
+
+    let mut entries: Vec<Entry> = history
+        .lines()
+        .filter_map(|line| JsonValue::from_str(line).ok())
+        .filter_map(|json| Entry::try_from(&json).ok())
+        .filter(|entry| entry.day == day)
+        .collect();
+
+    entries.sort_by_key(|entry| entry.timestamp);
+    entries
+}
+
+/// Prints a sparkline per part and a row per entry, marking parts that got slower than
+/// the entry before by more than [`DEFAULT_THRESHOLD`] with `▲`.
+pub fn print(day: Day, entries: &[Entry]) {
+    println!(
+        "Timing history of day {day} of {} ({} runs):",
+        day.year(),
+        entries.len()
+    );
+    println!();
+
+    for (i, label) in LABELS.into_iter().enumerate() {
+        let values: Vec<Option<Duration>> = entries.iter().map(|entry| entry.parts()[i]).collect();
+        let mut present = values.iter().flatten();
+        let Some(first) = present.next() else {
+            continue;
+        };
+        let last = present.last().unwrap_or(first);
+
+        println!(
+            "{label:<6}  {}  {first:.1?} → {last:.1?}",
+            sparkline(&values)
+        );
+    }
+
+    println!();
+    println!(
+        "{:<16}  {:<8}  {:<12}{:<12}{:<12}Environment",
+        "Date", "Commit", LABELS[0], LABELS[1], LABELS[2]
+    );
+
+    for (i, entry) in entries.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| entries[i].parts());
+
+        let parts: String = entry
+            .parts()
+            .into_iter()
+            .enumerate()
+            .map(|(part, duration)| {
+                let Some(duration) = duration else {
+                    return format!("{:<12}", "-");
+                };
+                let slower = previous
+                    .and_then(|previous| previous[part])
+                    .is_some_and(|baseline| {
+                        PartDelta {
+                            day,
+                            label: LABELS[part],
+                            baseline,
+                            current: duration,
+                        }
+                        .is_regression(DEFAULT_THRESHOLD)
+                    });
+                let cell = format!("{duration:.1?}{}", if slower { " ▲" } else { "" });
+                format!("{cell:<12}")
+            })
+            .collect();
+
+        let environment = &entry.environment;
+        let commit = format!(
+            "{}{}",
+            environment.commit.as_deref().unwrap_or("-"),
+            if environment.dirty { "*" } else { "" }
+        );
+
+        println!(
+            "{}  {commit:<8}  {parts}{}, {} threads, {}, {}",
+            format_date(entry.timestamp),
+            environment.profile,
+            environment.threads,
+            environment.rustc.as_deref().unwrap_or("unknown rustc"),
+            environment.cpu.as_deref().unwrap_or("unknown CPU"),
+        );
+    }
+
+    println!();
+    println!(
+        "`*` marks commits with uncommitted changes, `▲` parts that got slower than the run before."
+    );
+}
+
+/// Renders durations as a line of bars scaled between their minimum and maximum, gaps as spaces.
+fn sparkline(values: &[Option<Duration>]) -> String {
+    let present = values.iter().flatten();
+    let (Some(min), Some(max)) = (present.clone().min(), present.max()) else {
+        return " ".repeat(values.len());
+    };
+
+    values
+        .iter()
+        .map(|value| match value {
+            None => ' ',
+            Some(_) if max == min => SPARKS[0],
+            Some(value) => {
+                let range = (*max - *min).as_nanos();
+                let offset = (*value - *min).as_nanos();
+                #[allow(clippy::cast_possible_truncation)]
+                let index = (offset * (SPARKS.len() as u128 - 1) / range) as usize;
+                SPARKS[index]
+            }
+        })
+        .collect()
+}
+
+/// Formats a unix timestamp as a UTC date and time, e.g. `2025-12-09 05:00`.
+fn format_date(timestamp: u64) -> String {
+    let (days, seconds) = (timestamp / 86400, timestamp % 86400);
+
+    // NOTE: converts days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Entry> for JsonValue {
+    fn from(value: &Entry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let string =
+            |value: &Option<String>| value.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.day.year())),
+        );
+
+        let environment = &value.environment;
+        map.insert("commit".into(), string(&environment.commit));
+        map.insert("dirty".into(), JsonValue::Boolean(environment.dirty));
+        map.insert("rustc".into(), string(&environment.rustc));
+        map.insert("cpu".into(), string(&environment.cpu));
+        map.insert(
+            "profile".into(),
+            JsonValue::String(environment.profile.clone()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "threads".into(),
+            JsonValue::Number(environment.threads as f64),
+        );
+
+        for (key, duration) in [
+            ("parse_nanos", value.parse),
+            ("part_1_nanos", value.part_1),
+            ("part_2_nanos", value.part_2),
+        ] {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert(
+                key.into(),
+                duration.map_or(JsonValue::Null, |d| JsonValue::Number(d.as_nanos() as f64)),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Entry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected history entry.{key} to be a number."))
+        };
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let nanos = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<f64>()
+                .map(|nanos| Some(Duration::from_nanos(*nanos as u64)))
+                .ok_or(format!(
+                    "Expected history entry.{key} to be null or a number."
+                )),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<f64>())
+            .map_or(DEFAULT_YEAR, |year| *year as u16);
+
+        let day = string("day")
+            .and_then(|day| Day::parse(&day, year).ok())
+            .ok_or("Expected history entry.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Entry {
+            timestamp: number("timestamp")? as u64,
+            day,
+            environment: Environment {
+                commit: string("commit"),
+                dirty: json
+                    .get("dirty")
+                    .and_then(|v| v.get::<bool>())
+                    .copied()
+                    .unwrap_or_default(),
+                rustc: string("rustc"),
+                cpu: string("cpu"),
+                profile: string("profile").unwrap_or_else(|| "unknown".into()),
+                threads: number("threads")? as usize,
+            },
+            parse: nanos("parse_nanos")?,
+            part_1: nanos("part_1_nanos")?,
+            part_2: nanos("part_2_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{Entry, Environment, cpu_model, format_date, sparkline};
+    use crate::template::Day;
+
+    fn environment() -> Environment {
+        Environment {
+            commit: Some("a1b2c3d".into()),
+            dirty: true,
+            rustc: Some("rustc 1.91.0 (f8297e351 2025-10-28)".into()),
+            cpu: None,
+            profile: "release".into(),
+            threads: 8,
+        }
+    }
+
+    #[test]
+    fn renders_sparklines() {
+        let millis = |ms: &[Option<u64>]| -> Vec<Option<Duration>> {
+            ms.iter().map(|ms| ms.map(Duration::from_millis)).collect()
+        };
+
+        assert_eq!(sparkline(&millis(&[Some(1), Some(8), Some(15)])), "▁▄█");
+        assert_eq!(sparkline(&millis(&[Some(3), None, Some(3)])), "▁ ▁");
+        assert_eq!(sparkline(&millis(&[None, None])), "  ");
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01 00:00");
+        assert_eq!(format_date(1_733_029_200), "2024-12-01 05:00");
+        assert_eq!(format_date(1_709_210_096), "2024-02-29 12:34");
+    }
+
+    #[test]
+    fn finds_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD Ryzen 7 7840U\n\nprocessor\t: 1\nmodel name\t: AMD Ryzen 7 7840U\n";
+        assert_eq!(cpu_model(cpuinfo), Some("AMD Ryzen 7 7840U".into()));
+        assert_eq!(cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn serializes_entries() {
+        let entry = Entry {
+            timestamp: 1_733_029_200,
+            day: Day::of_year(2024, 9).unwrap(),
+            environment: environment(),
+            parse: None,
+            part_1: Some(Duration::from_nanos(1_234_567)),
+            part_2: Some(Duration::from_micros(89)),
+        };
+
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        assert!(!line.contains('\n'));
+
+        let json: JsonValue = line.parse().unwrap();
+        assert_eq!(Entry::try_from(&json).unwrap(), entry);
+    }
+}
@@ -0,0 +1,139 @@
+/// Real puzzle inputs of a day. Besides the default `data/{year}/inputs/{day}.txt`, a day can have
+/// any number of named inputs in `data/{year}/inputs/{day}/{name}.txt`, e.g. one per team member.
+use std::{fs, path::PathBuf};
+
+use crate::template::{
+    ANSI_ITALIC, ANSI_RESET, Day, data_dir, protocol::PartRecord, registry::Solution,
+    runner::RunOptions,
+};
+
+/// Name that refers to the default input, e.g. in `--input default`.
+pub const DEFAULT_INPUT: &str = "default";
+
+/// A puzzle input file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input {
+    /// `None` for the default input `data/{year}/inputs/{day}.txt`.
+    pub name: Option<String>,
+    pub path: PathBuf,
+}
+
+impl Input {
+    /// Name shown when reporting results, [`DEFAULT_INPUT`] for the default input.
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(DEFAULT_INPUT)
+    }
+}
+
+/// Results of running a solution against one input.
+pub struct InputRun {
+    pub input: Input,
+    pub records: Vec<PartRecord>,
+}
+
+/// Lists the inputs of a day: the default input first, then the named inputs sorted by name.
+pub fn list(day: Day) -> Vec<Input> {
+    let mut inputs = vec![];
+    let dir = data_dir(day.year());
+
+    let default = PathBuf::from(format!("{dir}/inputs/{day}.txt"));
+    if default.is_file() {
+        inputs.push(Input {
+            name: None,
+            path: default,
+        });
+    }
+
+    let mut named: Vec<Input> = fs::read_dir(format!("{dir}/inputs/{day}"))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            let name = path.file_stem()?.to_str()?.to_string();
+            Some(Input {
+                name: Some(name),
+                path,
+            })
+        })
+        .collect();
+
+    named.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    inputs.extend(named);
+    inputs
+}
+
+/// Finds the input selected with `--input <name>`.
+pub fn find(day: Day, name: &str) -> Option<Input> {
+    list(day).into_iter().find(|input| input.label() == name)
+}
+
+/// Run `solution` against every input of its day, or only against `options.input` if set.
+/// When a day has more than one input, each run is preceded by the name of its input.
+///
+/// Prints `Missing input.` and returns no runs if there is nothing to run against.
+pub fn run_each(solution: &Solution, options: &RunOptions) -> Vec<InputRun> {
+    let inputs = match &options.input {
+        Some(name) => find(solution.day, name).into_iter().collect(),
+        None => list(solution.day),
+    };
+
+    if inputs.is_empty() {
+        match &options.input {
+            Some(name) => println!("Missing input `{name}`."),
+            None => println!("Missing input."),
+        }
+        return vec![];
+    }
+
+    let show_names = inputs.len() > 1 || inputs[0].name.is_some();
+
+    inputs
+        .into_iter()
+        .filter_map(|input| {
+            let Ok(content) = fs::read_to_string(&input.path) else {
+                println!("Could not read {}.", input.path.display());
+                return None;
+            };
+
+            if show_names && !options.json {
+                println!("{ANSI_ITALIC}Input: {}{ANSI_RESET}", input.label());
+            }
+
+            // NOTE: the answer store and submission need to know which input is being run.
+            let options = RunOptions {
+                input: Some(input.label().to_string()),
+                ..options.clone()
+            };
+
+            let records = (solution.run)(content.into(), &options);
+            Some(InputRun { input, records })
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Input;
+
+    #[test]
+    fn labels_inputs() {
+        let input = Input {
+            name: None,
+            path: PathBuf::from("data/2025/inputs/01.txt"),
+        };
+        assert_eq!(input.label(), "default");
+
+        let input = Input {
+            name: Some("alice".into()),
+            path: PathBuf::from("data/2025/inputs/01/alice.txt"),
+        };
+        assert_eq!(input.label(), "alice");
+    }
+}
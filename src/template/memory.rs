@@ -0,0 +1,162 @@
+/// Lightweight allocation counting per solution part, enabled with the `count-allocs` feature.
+/// Unlike `--dhat`, this works with the regular profiles and is reported next to the timings.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(all(feature = "count-allocs", feature = "dhat-heap"))]
+compile_error!("the `count-allocs` and `dhat-heap` features both install a global allocator.");
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Allocations made while running a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Total number of bytes allocated.
+    pub bytes: u64,
+    /// Highest number of bytes that were live at the same time.
+    pub peak_bytes: u64,
+}
+
+/// A global allocator that forwards to [`System`] and keeps count of allocations.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        LIVE_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            LIVE_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    let size = size as u64;
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+/// Counter values at the start of a measurement.
+pub struct Snapshot {
+    allocations: u64,
+    bytes: u64,
+    live_bytes: u64,
+}
+
+/// Whether allocations are counted, i.e. the `count-allocs` feature is enabled.
+/// The counting allocator adds overhead to every allocation, so timings taken with it are not comparable.
+pub fn is_enabled() -> bool {
+    cfg!(feature = "count-allocs")
+}
+
+/// Start measuring allocations. Returns `None` if the `count-allocs` feature is disabled.
+///
+/// NOTE: the counters are process-wide, so allocations of other threads are included.
+pub fn start() -> Option<Snapshot> {
+    if !is_enabled() {
+        return None;
+    }
+
+    let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(live_bytes, Ordering::Relaxed);
+
+    Some(Snapshot {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        live_bytes,
+    })
+}
+
+/// Finish a measurement started with [`start`].
+pub fn finish(snapshot: &Snapshot) -> AllocStats {
+    AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - snapshot.allocations,
+        bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - snapshot.bytes,
+        peak_bytes: PEAK_BYTES
+            .load(Ordering::Relaxed)
+            .saturating_sub(snapshot.live_bytes),
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_bytes;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[cfg(feature = "count-allocs")]
+    #[test]
+    fn counts_allocations() {
+        let snapshot = super::start().unwrap();
+        let data = std::hint::black_box(vec![0_u8; 4096]);
+        drop(data);
+        let stats = super::finish(&snapshot);
+
+        assert!(stats.allocations >= 1);
+        assert!(stats.bytes >= 4096);
+        assert!(stats.peak_bytes >= 4096);
+    }
+}
@@ -1,68 +1,140 @@
-use std::{env, fs};
+use std::{
+    env, fs,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
+pub mod inputs;
+pub mod memory;
+pub mod progress;
+pub mod protocol;
+pub mod registry;
 pub mod runner;
+pub mod submissions;
+pub mod templates;
+#[cfg(feature = "today")]
+pub mod unlock;
 
 pub use day::*;
+pub use part::*;
+pub use selector::*;
 
+mod compare;
 mod day;
+mod history;
+mod part;
 mod readme_benchmarks;
 mod run_multi;
+mod scaffold;
+mod selector;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of the day's year to a string, e.g. `data/2025/examples/01.txt`.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(data_dir(day.year()))
+        .join(folder)
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, day: Day, part: Part) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(day.year()))
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Directory that holds the inputs, examples, puzzles and answers of a year, e.g. `data/2025`.
+pub fn data_dir(year: u16) -> String {
+    format!("data/{year}")
+}
+
+/// Current time in seconds since the unix epoch.
+pub fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternatively, `parse = <fn>` declares a function that turns the input into a value shared by both parts.
+/// It is timed once, separately from the parts, which then receive a reference to its output:
+/// `solution!(1, parse = parse_input)` expects `part_one(input: &Parsed)` and `part_two(input: &Parsed)`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, One] [part_two, Two]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, One]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, Two]);
+    };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, One] [part_two, Two]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+    (@impl $day:expr, $( [$func:expr, $part:ident] )*) => {
+        $crate::solution!(@common $day, |input, options| {
+            use $crate::template::runner::*;
+            vec![$( run_part($func, ::std::sync::Arc::clone(&input), DAY, $crate::template::Part::$part, options), )*]
+        });
+    };
+
+    (@impl $day:expr, parse = $parse:expr, $( [$func:expr, $part:ident] )*) => {
+        $crate::solution!(@common $day, |input, options| {
+            use $crate::template::runner::*;
+            let (parsed, record) = run_parse($parse, input, options);
+            let mut records = vec![record];
+            if let Some(parsed) = parsed {
+                // NOTE: parts run on their own thread, which shares the parsed input with this one.
+                let parsed = ::std::sync::Arc::new(parsed);
+                $( records.push(run_part($func, ::std::sync::Arc::clone(&parsed), DAY, $crate::template::Part::$part, options)); )*
+            }
+            records
+        });
+    };
+
+    (@common $day:expr, $run:expr) => {
+        /// The current day. Its year is taken from the file name, e.g. `src/bin/2025-01.rs`.
+        const DAY: $crate::template::Day = $crate::template::Day::of_year(
+            $crate::template::Day::year_of_file(file!()),
+            $day,
+        )
+        .expect("invalid day number, expecting a day of the calendar of the solution's year");
+
+        /// Runs every part of this day, shared by `main` and the in-process solution registry.
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution { day: DAY, run: $run };
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let options = $crate::template::runner::RunOptions::from_args();
+            $crate::template::inputs::run_each(&SOLUTION, &options);
         }
     };
 }
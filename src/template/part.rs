@@ -0,0 +1,100 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A part of a puzzle. Every day has two parts, the second one unlocks after solving the first.
+///
+/// # Display
+/// This value displays as its number.
+///
+/// ```
+/// # use advent_of_code::template::Part;
+/// assert_eq!(Part::Two.to_string(), "2");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One = 1,
+    Two = 2,
+}
+
+impl Part {
+    /// Both parts, in order.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// Creates a [`Part`] from its number if it's 1 or 2, returns [`None`] otherwise.
+    pub const fn new(part: u8) -> Option<Self> {
+        match part {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    /// Converts the [`Part`] into its number.
+    pub const fn into_inner(self) -> u8 {
+        self as u8
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.into_inner())
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        part.into_inner()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Part {
+    type Err = PartFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().ok().and_then(Self::new).ok_or(PartFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Part`].
+#[derive(Debug)]
+pub struct PartFromStrError;
+
+impl Error for PartFromStrError {}
+
+impl Display for PartFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a part number, either 1 or 2")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Part;
+
+    #[test]
+    fn parses_parts() {
+        assert_eq!(Part::from_str("1").unwrap(), Part::One);
+        assert_eq!(Part::from_str("2").unwrap(), Part::Two);
+        assert_eq!(
+            Part::from_str("3").unwrap_err().to_string(),
+            "expecting a part number, either 1 or 2"
+        );
+        assert!(Part::from_str("one").is_err());
+    }
+
+    #[test]
+    fn converts_parts() {
+        assert_eq!(Part::new(2), Some(Part::Two));
+        assert_eq!(Part::new(0), None);
+        assert_eq!(u8::from(Part::One), 1);
+        assert_eq!(Part::Two.to_string(), "2");
+        assert!(Part::One < Part::Two);
+    }
+}
@@ -0,0 +1,374 @@
+/// Tracks which parts are solved per day of a year and renders them into the stars table of the readme.
+///
+/// Stars come from two sources: correct answers in the submission history and the calendar page
+/// of the website, which is synced to `data/{year}/progress.json` with `cargo progress --sync`.
+/// Like the benchmarks, each year has its own table, e.g. `<!--- advent_readme_stars table 2025 --->`.
+use std::{collections::BTreeMap, collections::HashMap, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day, Part,
+    aoc_client::{AocError, Client, SubmitResult},
+    data_dir,
+    readme_benchmarks::{self, locate_year_table, year_marker},
+    submissions::History,
+};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Number of stars per day of a year. Days without stars are not listed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    pub year: u16,
+    pub stars: BTreeMap<Day, u8>,
+}
+
+impl Progress {
+    pub fn new(year: u16) -> Self {
+        Self {
+            year,
+            stars: BTreeMap::new(),
+        }
+    }
+
+    fn path(year: u16) -> String {
+        format!("{}/progress.json", data_dir(year))
+    }
+
+    /// Read the synced progress of `year` and add the parts solved by submissions since.
+    ///
+    /// Fails if `data/{year}/progress.json` exists but cannot be read or parsed.
+    pub fn read(year: u16) -> Result<Self, String> {
+        let mut progress = Self::read_stored(year)?;
+        match History::read() {
+            Ok(history) => progress.merge_history(&history),
+            Err(e) => eprintln!("Ignoring the submission history: {e}"),
+        }
+        Ok(progress)
+    }
+
+    fn read_stored(year: u16) -> Result<Self, String> {
+        let path = Self::path(year);
+        match fs::read_to_string(&path) {
+            Ok(s) => Progress::parse(&s, year)
+                .map_err(|e| format!("\"{path}\" is corrupt, please fix or remove it: {e}")),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Progress::new(year)),
+            Err(e) => Err(format!("failed to read \"{path}\": {e}")),
+        }
+    }
+
+    pub fn store(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(Self::path(self.year))?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, day: Day) -> u8 {
+        self.stars.get(&day).copied().unwrap_or_default()
+    }
+
+    /// Mark `part` of `day` as solved. Solving part two implies part one was solved.
+    pub fn solve(&mut self, day: Day, part: Part) {
+        let stars = self.stars.entry(day).or_default();
+        *stars = (*stars).max(part.into_inner());
+    }
+
+    /// Add the parts of this year that were solved by a submission.
+    pub fn merge_history(&mut self, history: &History) {
+        for submission in &history.submissions {
+            if submission.day.year() == self.year && submission.result == SubmitResult::Correct {
+                self.solve(submission.day, submission.part);
+            }
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.stars.values().map(|&stars| u32::from(stars)).sum()
+    }
+}
+
+/// Download the calendar of `year`, store its stars to `data/{year}/progress.json`
+/// and return the current progress.
+pub fn sync(year: u16) -> Result<Progress, AocError> {
+    let html = Client::from_env()?.calendar(year)?;
+
+    let mut stored = Progress::read_stored(year).map_err(invalid_data)?;
+    for (day, stars) in parse_calendar(&html, year) {
+        if let Some(part) = Part::new(stars) {
+            stored.solve(day, part);
+        }
+    }
+    stored.store()?;
+
+    Progress::read(year).map_err(invalid_data)
+}
+
+fn invalid_data(e: String) -> AocError {
+    AocError::Io(io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Parses the stars of each day from the calendar page of `year`, e.g. from
+/// `<a aria-label="Day 1, two stars" href="/2025/day/1" class="calendar-day1 calendar-verycomplete">`.
+pub fn parse_calendar(html: &str, year: u16) -> Vec<(Day, u8)> {
+    let mut days = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<a ") {
+        let tag = &rest[start..];
+        let end = tag.find('>').unwrap_or(tag.len());
+        let classes = tag[..end]
+            .split("class=\"")
+            .nth(1)
+            .and_then(|x| x.split('"').next())
+            .unwrap_or_default();
+
+        let day = classes
+            .split_whitespace()
+            .find_map(|class| class.strip_prefix("calendar-day"))
+            .and_then(|x| Day::parse(x, year).ok());
+
+        if let Some(day) = day {
+            let stars = classes
+                .split_whitespace()
+                .map(|class| match class {
+                    "calendar-verycomplete" => 2,
+                    "calendar-complete" => 1,
+                    _ => 0,
+                })
+                .max()
+                .unwrap_or_default();
+            days.push((day, stars));
+        }
+
+        rest = &tag[end..];
+    }
+
+    days
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn construct_table(progress: &Progress, marker_year: Option<u16>) -> String {
+    let year = progress.year;
+    let marker = match marker_year {
+        Some(year) => year_marker(MARKER, year),
+        None => MARKER.to_string(),
+    };
+
+    let mut lines: Vec<String> = vec![
+        marker.clone(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for (day, &stars) in &progress.stars {
+        let link = format!(
+            "[Day {0}](https://adventofcode.com/{year}/day/{0})",
+            day.into_inner()
+        );
+        let star = |part: u8| if stars >= part { "⭐" } else { " " };
+        lines.push(format!("| {link} | {} | {} |", star(1), star(2)));
+    }
+
+    lines.push(marker);
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, progress: &Progress) -> Result<(), readme_benchmarks::Error> {
+    let (positions, marker_year) = locate_year_table(s, MARKER, progress.year)?;
+    let table = construct_table(progress, marker_year);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Render the stars table into the readme.
+pub fn update_readme(progress: &Progress) -> Result<(), readme_benchmarks::Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, progress)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Progress> for JsonValue {
+    fn from(value: &Progress) -> Self {
+        let stars: HashMap<String, JsonValue> = value
+            .stars
+            .iter()
+            .map(|(day, &stars)| (day.to_string(), JsonValue::Number(f64::from(stars))))
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("year".into(), JsonValue::Number(f64::from(value.year)));
+        map.insert("stars".into(), JsonValue::Object(stars));
+        JsonValue::Object(map)
+    }
+}
+
+impl Progress {
+    /// Parses the progress stored in `data/{year}/progress.json`.
+    ///
+    /// NOTE: files written before the year was stored only contain day numbers,
+    /// their location determines the year.
+    pub fn parse(value: &str, year: u16) -> Result<Self, String> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON file."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<f64>())
+            .map_or(year, |year| *year as u16);
+
+        let stars = json
+            .get("stars")
+            .ok_or("expected JSON document to have key `stars`.")?
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected `json.stars` to be an object.")?;
+
+        let stars = stars
+            .iter()
+            .map(|(day, stars)| {
+                let day = Day::parse(day, year).map_err(|e| e.to_string())?;
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let stars = stars
+                    .get::<f64>()
+                    .map(|x| *x as u8)
+                    .ok_or(format!("expected `json.stars.{day}` to be a number."))?;
+                Ok((day, stars))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Progress { year, stars })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{MARKER, Progress, parse_calendar, update_content, year_marker};
+    use crate::{
+        day,
+        template::{
+            DEFAULT_YEAR, Part,
+            aoc_client::SubmitResult,
+            submissions::{History, Submission},
+        },
+    };
+
+    fn get_mock_progress() -> Progress {
+        let mut progress = Progress::new(DEFAULT_YEAR);
+        progress.solve(day!(1), Part::Two);
+        progress.solve(day!(3), Part::One);
+        progress
+    }
+
+    #[test]
+    fn merges_submissions() {
+        let mut progress = get_mock_progress();
+        let submission = |day, part, result| Submission {
+            day,
+            part,
+            answer: "1".into(),
+            result,
+            timestamp: 0,
+        };
+        progress.merge_history(&History {
+            submissions: vec![
+                submission(day!(3), Part::Two, SubmitResult::Correct),
+                submission(day!(4), Part::One, SubmitResult::Correct),
+                submission(day!(5), Part::One, SubmitResult::WrongLevel),
+                submission(day!(1), Part::One, SubmitResult::Correct),
+                submission(day!(6).with_year(2015), Part::One, SubmitResult::Correct),
+            ],
+        });
+
+        assert_eq!(progress.get(day!(1)), 2);
+        assert_eq!(progress.get(day!(3)), 2);
+        assert_eq!(progress.get(day!(4)), 1);
+        assert_eq!(progress.get(day!(5)), 0);
+        assert_eq!(progress.get(day!(6).with_year(2015)), 0);
+        assert_eq!(progress.total(), 5);
+    }
+
+    #[test]
+    fn parses_calendar() {
+        let html = r#"<pre class="calendar"><a aria-label="Day 1, two stars" href="/2025/day/1" class="calendar-day1 calendar-verycomplete">a</a>
+<a aria-label="Day 2, one star" href="/2025/day/2" class="calendar-day2 calendar-complete">b</a>
+<a aria-label="Day 3" href="/2025/day/3" class="calendar-day3">c</a>
+<a href="/2025/about">d</a></pre>"#;
+
+        assert_eq!(
+            parse_calendar(html, 2015),
+            vec![
+                (day!(1).with_year(2015), 2),
+                (day!(2).with_year(2015), 1),
+                (day!(3).with_year(2015), 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn updates_stars_table() {
+        let mut s = format!("foo\nbar\n{MARKER}\n{MARKER}\nbaz");
+        let mut progress = Progress::new(2025);
+        progress.solve(day!(1).with_year(2025), Part::Two);
+        progress.solve(day!(3).with_year(2025), Part::One);
+
+        update_content(&mut s, &progress).unwrap();
+        let expected = [
+            "foo",
+            "bar",
+            MARKER,
+            "## 2025 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2025/day/1) | ⭐ | ⭐ |",
+            "| [Day 3](https://adventofcode.com/2025/day/3) | ⭐ |   |",
+            MARKER,
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+
+        let marker = year_marker(MARKER, 2025);
+        let mut s = format!("{MARKER}\n{MARKER}\n{marker}\n{marker}");
+        update_content(&mut s, &progress).unwrap();
+        assert!(s.starts_with(&format!("{MARKER}\n{MARKER}\n{marker}\n## 2025 Results")));
+    }
+
+    #[test]
+    fn uses_year_of_file_for_progress_without_year() {
+        let json = r#"{ "stars": { "01": 2, "13": 1 } }"#;
+        let progress = Progress::parse(json, 2024).unwrap();
+        assert_eq!(progress.year, 2024);
+        assert_eq!(progress.total(), 3);
+        assert_eq!(
+            progress
+                .stars
+                .keys()
+                .map(|day| day.year())
+                .collect::<Vec<_>>(),
+            vec![2024, 2024]
+        );
+
+        assert!(Progress::parse(json, 2025).is_err());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let progress = get_mock_progress();
+        let json = JsonValue::from(&progress).stringify().unwrap();
+        assert_eq!(Progress::parse(&json, DEFAULT_YEAR).unwrap(), progress);
+    }
+}
@@ -0,0 +1,435 @@
+/// Structured records that solution binaries emit when run by the orchestrator (`--json`).
+/// Every part (and the optional parse phase) is written to stdout as a single line of JSON.
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Part,
+    answers::Verdict,
+    memory::AllocStats,
+    runner::{BenchStats, Failure},
+};
+
+/// What a record was measured for: the parse function declared via `solution!` or a part.
+///
+/// # Display
+/// This value displays as it is exchanged, i.e. `parse`, `1` or `2`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl Phase {
+    /// Human-readable label, e.g. `Part 1`.
+    pub fn label(self) -> String {
+        match self {
+            Phase::Parse => "Parse".into(),
+            Phase::Part(part) => format!("Part {part}"),
+        }
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => f.write_str("parse"),
+            Phase::Part(part) => write!(f, "{part}"),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            x => x
+                .parse()
+                .map(Phase::Part)
+                .map_err(|_| format!("unknown record part `{x}`.")),
+        }
+    }
+}
+
+/// Outcome of running a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part panicked.
+    Panicked,
+    /// The part exceeded its time budget.
+    TimedOut,
+}
+
+impl Status {
+    /// Whether the part failed to run to completion.
+    pub fn is_failure(self) -> bool {
+        matches!(self, Status::Panicked | Status::TimedOut)
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Panicked => "panicked",
+            Status::TimedOut => "timed_out",
+        })
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "panicked" => Ok(Status::Panicked),
+            "timed_out" => Ok(Status::TimedOut),
+            x => Err(format!("unknown record status `{x}`.")),
+        }
+    }
+}
+
+/// Result of running one part, as exchanged between a solution binary and `run_multi`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub part: Phase,
+    pub answer: Option<String>,
+    pub nanos: u64,
+    pub samples: u128,
+    pub status: Status,
+    pub error: Option<String>,
+    pub stats: Option<BenchStats>,
+    /// Allocations of the first run, with the `count-allocs` feature.
+    pub memory: Option<AllocStats>,
+    /// Comparison against the known answer in `data/answers`.
+    pub verdict: Verdict,
+    /// Whether the part was timed. Parts that run while a part that timed out is still running are not.
+    pub timed: bool,
+}
+
+impl PartRecord {
+    pub fn new(
+        part: Phase,
+        answer: Option<String>,
+        duration: Duration,
+        stats: Option<BenchStats>,
+    ) -> Self {
+        let status = if answer.is_some() || part == Phase::Parse {
+            Status::Solved
+        } else {
+            Status::Unsolved
+        };
+
+        Self {
+            part,
+            answer,
+            #[allow(clippy::cast_possible_truncation)]
+            nanos: duration.as_nanos() as u64,
+            samples: stats.map_or(1, |s| s.samples),
+            status,
+            error: None,
+            stats,
+            memory: None,
+            verdict: Verdict::Unknown,
+            timed: true,
+        }
+    }
+
+    /// Creates the record of a part that did not produce a result.
+    pub fn failed(part: Phase, failure: &Failure) -> Self {
+        let (status, duration, error) = match failure {
+            Failure::Panicked(message) => (Status::Panicked, Duration::ZERO, message.clone()),
+            Failure::TimedOut(budget) => (Status::TimedOut, *budget, failure.to_string()),
+        };
+
+        Self {
+            status,
+            error: Some(error),
+            ..Self::new(part, None, duration, None)
+        }
+    }
+
+    /// Human-readable label of the part, e.g. `Part 1`.
+    pub fn label(&self) -> String {
+        self.part.label()
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.nanos)
+    }
+
+    /// Serializes the record to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("record should be serializable")
+    }
+
+    /// Parses a line of output, returns `None` for lines that are not records.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        let json = JsonValue::from_str(line.trim()).ok()?;
+        PartRecord::try_from(&json).ok()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: Option<&String>) -> JsonValue {
+    value.cloned().map_or(JsonValue::Null, JsonValue::String)
+}
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::String(value.part.to_string()));
+        map.insert("answer".into(), optional_string(value.answer.as_ref()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("nanos".into(), JsonValue::Number(value.nanos as f64));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert("error".into(), optional_string(value.error.as_ref()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        map.insert("timed".into(), JsonValue::Boolean(value.timed));
+        if let Verdict::Incorrect { expected } = &value.verdict {
+            map.insert("expected".into(), JsonValue::String(expected.clone()));
+        }
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .map(|v| {
+                    if v.is_null() {
+                        Some(None)
+                    } else {
+                        v.get::<String>().cloned().map(Some)
+                    }
+                })
+                .ok_or(format!("Expected record.{key} to be present."))?
+                .ok_or(format!("Expected record.{key} to be null or string."))
+        };
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected record.{key} to be a number."))
+        };
+
+        let part = string("part")?
+            .ok_or("Expected record.part to be a string.")?
+            .parse()?;
+
+        let status = string("status")?
+            .ok_or("Expected record.status to be a string.")?
+            .parse()?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        let memory = match json.get("memory") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(AllocStats::try_from(v)?),
+        };
+
+        // NOTE: records without a verdict stem from binaries that predate the answer store.
+        let verdict = match json.get("verdict").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "correct" => Verdict::Correct,
+            Some(x) if x == "incorrect" => Verdict::Incorrect {
+                expected: string("expected")?.ok_or("Expected record.expected to be a string.")?,
+            },
+            _ => Verdict::Unknown,
+        };
+
+        // NOTE: records without this flag stem from binaries that always timed their parts.
+        let timed = match json.get("timed") {
+            None => true,
+            Some(v) => *v
+                .get::<bool>()
+                .ok_or("Expected record.timed to be a boolean.")?,
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (nanos, samples) = (number("nanos")? as u64, number("samples")? as u128);
+
+        Ok(PartRecord {
+            part,
+            answer: string("answer")?,
+            nanos,
+            samples,
+            status,
+            error: string("error")?,
+            stats,
+            memory,
+            verdict,
+            timed,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{PartRecord, Phase, Status};
+    use crate::template::{
+        Part,
+        answers::Verdict,
+        memory::AllocStats,
+        runner::{BenchStats, Failure},
+    };
+
+    #[test]
+    fn roundtrips_records() {
+        let stats = BenchStats::from_samples(&[Duration::from_nanos(10), Duration::from_nanos(20)]);
+        let record = PartRecord::new(
+            Phase::Part(Part::One),
+            Some("(2s @ 5 samples)\nsecond line".into()),
+            Duration::from_nanos(15),
+            Some(stats),
+        );
+
+        let line = record.to_json_line();
+        assert_eq!(line.contains('\n'), false);
+        assert_eq!(PartRecord::from_json_line(&line), Some(record));
+    }
+
+    #[test]
+    fn roundtrips_verdicts() {
+        let mut record = PartRecord::new(
+            Phase::Part(Part::One),
+            Some("41".into()),
+            Duration::from_nanos(1),
+            None,
+        );
+        record.verdict = Verdict::Incorrect {
+            expected: "42".into(),
+        };
+
+        let line = record.to_json_line();
+        assert_eq!(PartRecord::from_json_line(&line), Some(record));
+    }
+
+    #[test]
+    fn roundtrips_memory() {
+        let mut record = PartRecord::new(Phase::Parse, None, Duration::from_nanos(1), None);
+        record.memory = Some(AllocStats {
+            allocations: 3,
+            bytes: 4096,
+            peak_bytes: 2048,
+        });
+
+        let line = record.to_json_line();
+        assert_eq!(PartRecord::from_json_line(&line), Some(record));
+    }
+
+    #[test]
+    fn marks_missing_answers_as_unsolved() {
+        let record = PartRecord::new(Phase::Part(Part::Two), None, Duration::from_millis(1), None);
+        assert_eq!(record.status, Status::Unsolved);
+        assert_eq!(record.samples, 1);
+        assert_eq!(record.label(), "Part 2");
+    }
+
+    #[test]
+    fn records_failures() {
+        let record = PartRecord::failed(
+            Phase::Part(Part::One),
+            &Failure::Panicked("Invalid parse".into()),
+        );
+        assert_eq!(record.status, Status::Panicked);
+        assert_eq!(record.error, Some("Invalid parse".into()));
+        assert_eq!(record.answer, None);
+
+        let record = PartRecord::failed(
+            Phase::Part(Part::Two),
+            &Failure::TimedOut(Duration::from_secs(2)),
+        );
+        assert_eq!(record.status, Status::TimedOut);
+        assert_eq!(record.duration(), Duration::from_secs(2));
+
+        let line = record.to_json_line();
+        assert_eq!(PartRecord::from_json_line(&line), Some(record));
+    }
+
+    #[test]
+    fn parses_phases() {
+        for phase in [Phase::Parse, Phase::Part(Part::One), Phase::Part(Part::Two)] {
+            assert_eq!(phase.to_string().parse::<Phase>(), Ok(phase));
+        }
+        assert_eq!(Phase::Part(Part::One).to_string(), "1");
+        assert!("3".parse::<Phase>().is_err());
+    }
+
+    #[test]
+    fn marks_parse_records_as_solved() {
+        let record = PartRecord::new(Phase::Parse, None, Duration::from_millis(1), None);
+        assert_eq!(record.status, Status::Solved);
+        assert_eq!(record.label(), "Parse");
+    }
+
+    #[test]
+    fn parses_records() {
+        let line = r#"{"part":"2","answer":"10","nanos":74130000,"samples":99999,"status":"solved","error":null,"stats":null}"#;
+        let record = PartRecord::from_json_line(line).unwrap();
+        assert_eq!(record.part, Phase::Part(Part::Two));
+        assert_eq!(record.answer, Some("10".into()));
+        assert_eq!(record.duration(), Duration::from_nanos(74_130_000));
+        assert_eq!(record.samples, 99999);
+        assert_eq!(record.status, Status::Solved);
+        assert_eq!(record.stats, None);
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(
+            PartRecord::from_json_line("Part 1: 0 (74.13ns @ 100000 samples)"),
+            None
+        );
+        assert_eq!(PartRecord::from_json_line(""), None);
+        assert_eq!(PartRecord::from_json_line(r#"{"debug": 1}"#), None);
+    }
+}
@@ -1,19 +1,36 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// Each year has its own table, marked by e.g. `<!--- benchmarking table 2025 --->`. Readmes that
+/// only track a single year can use the marker without a year instead.
+/// The columns, order and markup of the table are configured with [`TableConfig`].
+use std::{collections::BTreeMap, env, fmt::Display, fs, io, str::FromStr, time::Duration};
 
 use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::answers::Verdict;
+use crate::template::memory::{AllocStats, format_bytes};
+use crate::template::progress::Progress;
+use crate::template::protocol::Status;
+use crate::template::runner::BenchStats;
+use crate::template::timings::{Timing, Timings};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => f.write_str(e),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -21,17 +38,37 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(super) pos_start: usize,
+    pub(super) pos_end: usize,
 }
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    format!("./src/bin/{}.rs", day.bin_name())
+}
+
+/// Marker of the table of a year, e.g. `<!--- benchmarking table 2025 --->`.
+pub(super) fn year_marker(marker: &str, year: u16) -> String {
+    marker.replace(" --->", &format!(" {year} --->"))
+}
+
+/// Locates the table of `year`, falling back to the table marked without a year.
+/// Returns the position of the table and the year of its marker.
+pub(super) fn locate_year_table(
+    readme: &str,
+    marker: &str,
+    year: u16,
+) -> Result<(TablePosition, Option<u16>), Error> {
+    let marker_of_year = year_marker(marker, year);
+    if readme.contains(&marker_of_year) {
+        return Ok((locate_table(readme, &marker_of_year)?, Some(year)));
+    }
+    Ok((locate_table(readme, marker)?, None))
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates the table between two occurences of `marker`, including the markers.
+pub(super) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,76 +89,474 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+/// Shows the timing of a part, or why there is none if it failed.
+fn format_cell(mean: Option<Duration>, status: Option<Status>) -> String {
+    match (mean, status) {
+        (Some(mean), _) => format!("{mean:.1?}"),
+        (None, Some(status)) if status.is_failure() => {
+            format!("✖ {}", status.to_string().replace('_', " "))
+        }
+        (None, _) => "-".into(),
+    }
+}
+
+/// Shows the peak memory and allocation count of a part.
+fn format_memory_cell(memory: Option<AllocStats>) -> String {
+    match memory {
+        Some(memory) => format!(
+            "{} ({} allocs)",
+            format_bytes(memory.peak_bytes),
+            memory.allocations
+        ),
+        None => "-".into(),
+    }
+}
+
+/// Columns that can be shown next to the day, configured with `AOC_README_COLUMNS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Parse,
+    Part1,
+    Part2,
+    /// Peak memory and allocation count of both parts.
+    Memory,
+    /// Number of benchmark samples of both parts.
+    Samples,
+    /// Share of the day in the total time, with an inline bar.
+    Share,
+    /// Stars earned, see [`Progress`].
+    Stars,
+    /// Whether every timed part matched its known answer when it was timed, see [`Verdict`].
+    Verified,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "memory" => Ok(Column::Memory),
+            "samples" => Ok(Column::Samples),
+            "share" => Ok(Column::Share),
+            "stars" => Ok(Column::Stars),
+            "verified" => Ok(Column::Verified),
+            _ => Err(format!(
+                "unknown column `{s}`, expected one of parse, part_1, part_2, memory, samples, share, stars, verified."
+            )),
+        }
+    }
+}
+
+impl Column {
+    fn headers(self) -> &'static [&'static str] {
+        match self {
+            Column::Parse => &["Parse"],
+            Column::Part1 => &["Part 1"],
+            Column::Part2 => &["Part 2"],
+            Column::Memory => &["Part 1 Memory", "Part 2 Memory"],
+            Column::Samples => &["Part 1 Samples", "Part 2 Samples"],
+            Column::Share => &["Share"],
+            Column::Stars => &["Stars"],
+            Column::Verified => &["Verified"],
+        }
+    }
+
+    fn cells(self, timing: &Timing, facts: &Facts) -> Vec<Cell> {
+        match self {
+            Column::Parse => vec![Cell::Code(format_cell(
+                timing.parse_stats.map(|stats| stats.mean),
+                timing.parse_status,
+            ))],
+            Column::Part1 => vec![Cell::Code(format_cell(
+                timing.part_1_stats.map(|stats| stats.mean),
+                timing.part_1_status,
+            ))],
+            Column::Part2 => vec![Cell::Code(format_cell(
+                timing.part_2_stats.map(|stats| stats.mean),
+                timing.part_2_status,
+            ))],
+            Column::Memory => vec![
+                Cell::Code(format_memory_cell(timing.part_1_memory)),
+                Cell::Code(format_memory_cell(timing.part_2_memory)),
+            ],
+            Column::Samples => vec![
+                Cell::Code(format_samples_cell(timing.part_1_stats)),
+                Cell::Code(format_samples_cell(timing.part_2_stats)),
+            ],
+            Column::Share => vec![Cell::Text(format_share_cell(
+                timing.total_nanos,
+                facts.total_nanos,
+            ))],
+            Column::Stars => vec![Cell::Text(match facts.stars.get(&timing.day) {
+                Some(&stars) if stars > 0 => "⭐".repeat(stars.into()),
+                _ => "-".into(),
+            })],
+            Column::Verified => {
+                let timed = [
+                    (timing.part_1_stats, &timing.part_1_verdict),
+                    (timing.part_2_stats, &timing.part_2_verdict),
+                ]
+                .into_iter()
+                .filter(|(stats, _)| stats.is_some())
+                .map(|(_, verdict)| verdict)
+                .collect::<Vec<_>>();
+                let verified = !timed.is_empty()
+                    && timed
+                        .iter()
+                        .all(|verdict| matches!(verdict, Some(Verdict::Correct)));
+                vec![Cell::Text(if verified { "✔" } else { "-" }.into())]
+            }
+        }
+    }
+}
+
+/// Order of the days in the table, configured with `AOC_README_SORT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sort {
+    #[default]
+    Day,
+    /// Slowest day first.
+    Time,
+}
+
+impl FromStr for Sort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Sort::Day),
+            "time" => Ok(Sort::Time),
+            _ => Err(format!("unknown sort order `{s}`, expected day or time.")),
+        }
+    }
+}
+
+/// Markup of the table, configured with `AOC_README_FORMAT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Markdown,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            _ => Err(format!(
+                "unknown table format `{s}`, expected markdown or html."
+            )),
+        }
+    }
+}
+
+/// How the benchmark table is rendered. The table is always placed between the markers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableConfig {
+    /// Columns after the day. If not set, parse and both parts are shown, followed by the
+    /// memory columns once timings were recorded with the `count-allocs` feature.
+    pub columns: Option<Vec<Column>>,
+    pub sort: Sort,
+    pub format: Format,
+    /// Name the year in the heading even if the table is marked without a year.
+    pub year_heading: bool,
+}
+
+impl TableConfig {
+    /// Read the config from the environment, usually set in the `[env]` section of `.cargo/config.toml`:
+    ///  - `AOC_README_COLUMNS`: comma-separated columns, e.g. `parse,part_1,part_2,share`.
+    ///  - `AOC_README_SORT`: `day` or `time`.
+    ///  - `AOC_README_FORMAT`: `markdown` or `html`.
+    ///  - `AOC_README_YEAR_HEADING`: `true` or `false`.
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_vars(|key| env::var(key).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let columns = var("AOC_README_COLUMNS")
+            .map(|columns| {
+                columns
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|column| !column.is_empty())
+                    .map(str::parse)
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        let year_heading = match var("AOC_README_YEAR_HEADING").as_deref() {
+            None | Some("false") => false,
+            Some("true") => true,
+            Some(value) => {
+                return Err(format!(
+                    "unknown value `{value}` of AOC_README_YEAR_HEADING, expected true or false."
+                ));
+            }
+        };
+
+        Ok(Self {
+            columns,
+            sort: var("AOC_README_SORT")
+                .map(|sort| sort.parse())
+                .transpose()?
+                .unwrap_or_default(),
+            format: var("AOC_README_FORMAT")
+                .map(|format| format.parse())
+                .transpose()?
+                .unwrap_or_default(),
+            year_heading,
+        })
+    }
+
+    fn columns(&self, timings: &Timings) -> Vec<Column> {
+        if let Some(columns) = &self.columns {
+            return columns.clone();
+        }
+
+        // memory columns are only shown once timings were recorded with the `count-allocs` feature.
+        let has_memory = timings
+            .data
+            .iter()
+            .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some());
+
+        let mut columns = vec![Column::Parse, Column::Part1, Column::Part2];
+        if has_memory {
+            columns.push(Column::Memory);
+        }
+        columns
+    }
+
+    fn uses(&self, column: Column) -> bool {
+        self.columns
+            .as_ref()
+            .is_some_and(|columns| columns.contains(&column))
+    }
+}
+
+/// Facts about the days that are not part of their timings.
+#[derive(Default)]
+struct Facts {
+    total_nanos: f64,
+    stars: BTreeMap<Day, u8>,
+}
+
+enum Cell {
+    Code(String),
+    Text(String),
+    Link { label: String, href: String },
+}
+
+impl Cell {
+    fn render(&self, format: Format) -> String {
+        match (self, format) {
+            (Cell::Code(s), Format::Markdown) => format!("`{s}`"),
+            (Cell::Code(s), Format::Html) => format!("<code>{s}</code>"),
+            (Cell::Text(s), _) => s.clone(),
+            (Cell::Link { label, href }, Format::Markdown) => format!("[{label}]({href})"),
+            (Cell::Link { label, href }, Format::Html) => format!("<a href=\"{href}\">{label}</a>"),
+        }
+    }
+}
+
+/// Shows the number of samples a part was benched with.
+fn format_samples_cell(stats: Option<BenchStats>) -> String {
+    stats.map_or_else(|| "-".into(), |stats| stats.samples.to_string())
+}
+
+/// Shows the share of a day in the total time as a percentage and a bar of ten blocks.
+fn format_share_cell(nanos: f64, total_nanos: f64) -> String {
+    let share = if total_nanos > 0.0 {
+        nanos / total_nanos
+    } else {
+        0.0
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let filled = ((share * 10.0).round() as usize).min(10);
+    format!(
+        "{}{} {:.1}%",
+        "█".repeat(filled),
+        "░".repeat(10 - filled),
+        share * 100.0
+    )
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    year: u16,
+    marker_year: Option<u16>,
+    config: &TableConfig,
+    facts: &Facts,
+) -> String {
+    let marker = match marker_year {
+        Some(year) => year_marker(MARKER, year),
+        None => MARKER.to_string(),
+    };
+    let header = match marker_year.or(config.year_heading.then_some(year)) {
+        Some(year) => format!("{prefix} {year} Benchmarks"),
+        None => format!("{prefix} Benchmarks"),
+    };
+
+    let columns = config.columns(&timings);
+    let headers: Vec<&str> = std::iter::once("Day")
+        .chain(
+            columns
+                .iter()
+                .flat_map(|column| column.headers().iter().copied()),
+        )
+        .collect();
+
+    let mut data = timings.data;
+    if config.sort == Sort::Time {
+        data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    let rows: Vec<Vec<Cell>> = data
+        .iter()
+        .map(|timing| {
+            let day = Cell::Link {
+                label: format!("Day {}", timing.day.into_inner()),
+                href: get_path_for_bin(timing.day),
+            };
+            std::iter::once(day)
+                .chain(
+                    columns
+                        .iter()
+                        .flat_map(|column| column.cells(timing, facts)),
+                )
+                .collect()
+        })
+        .collect();
+
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+
+    match config.format {
+        Format::Markdown => {
+            lines.push(format!("| {} |", headers.join(" | ")));
+            lines.push(format!("| {}  |", vec![":---:"; headers.len()].join(" | ")));
+            for row in rows {
+                let cells: Vec<String> = row.iter().map(|c| c.render(Format::Markdown)).collect();
+                lines.push(format!("| {} |", cells.join(" | ")));
+            }
+        }
+        Format::Html => {
+            lines.push("<table>".into());
+            lines.push("  <thead>".into());
+            let headers: String = headers.iter().map(|h| format!("<th>{h}</th>")).collect();
+            lines.push(format!("    <tr>{headers}</tr>"));
+            lines.push("  </thead>".into());
+            lines.push("  <tbody>".into());
+            for row in rows {
+                let cells: String = row
+                    .iter()
+                    .map(|c| format!("<td>{}</td>", c.render(Format::Html)))
+                    .collect();
+                lines.push(format!("    <tr>{cells}</tr>"));
+            }
+            lines.push("  </tbody>".into());
+            lines.push("</table>".into());
+        }
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    year: u16,
+    config: &TableConfig,
+    facts: &Facts,
+) -> Result<(), Error> {
+    let (positions, marker_year) = locate_year_table(s, MARKER, year)?;
+    let table = construct_table(
+        "##",
+        timings,
+        total_millis,
+        year,
+        marker_year,
+        config,
+        facts,
+    );
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Render the timings of `year` into the readme, as configured by [`TableConfig::from_env`].
+pub fn update(timings: Timings, year: u16) -> Result<(), Error> {
+    let config = TableConfig::from_env().map_err(Error::Parser)?;
+
+    let facts = Facts {
+        total_nanos: timings.data.iter().map(|t| t.total_nanos).sum(),
+        stars: if config.uses(Column::Stars) {
+            Progress::read(year).map_err(Error::Parser)?.stars
+        } else {
+            BTreeMap::new()
+        },
+    };
+
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, year, &config, &facts)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
+    use std::{collections::BTreeMap, time::Duration};
+
+    use super::{
+        Column, Facts, Format, MARKER, Sort, TableConfig, format_cell, format_share_cell,
+        update_content, year_marker,
+    };
+    use crate::template::{
+        answers::Verdict, memory::AllocStats, protocol::Status, runner::BenchStats,
+    };
     use crate::{day, template::timings::Timing, template::timings::Timings};
 
+    /// A part that was benched `samples` times with a mean of `nanos`.
+    fn stats(nanos: u64, samples: u128) -> Option<BenchStats> {
+        Some(BenchStats {
+            samples,
+            ..BenchStats::from_samples(&[Duration::from_nanos(nanos)])
+        })
+    }
+
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1_stats: stats(10_000_000, 1),
+                    part_2_stats: stats(20_000_000, 1),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1).with_year(2025))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse_stats: stats(5_000_000, 1),
+                    part_1_stats: stats(30_000_000, 1),
+                    part_2_stats: stats(40_000_000, 1),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2).with_year(2025))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1_stats: stats(40_000_000, 1),
+                    part_2_stats: stats(50_000_000, 1),
                     total_nanos: 9e+10,
+                    ..Timing::new(day!(4).with_year(2025))
                 },
             ],
         }
@@ -131,28 +566,68 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            2025,
+            &TableConfig::default(),
+            &Facts::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            2025,
+            &TableConfig::default(),
+            &Facts::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            2025,
+            &TableConfig::default(),
+            &Facts::default(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            2025,
+            &TableConfig::default(),
+            &Facts::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            2025,
+            &TableConfig::default(),
+            &Facts::default(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,18 +635,26 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            2025,
+            &TableConfig::default(),
+            &Facts::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2025-01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2025-02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2025-04.rs) | `-` | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +663,187 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn updates_table_of_year() {
+        let marker_2024 = year_marker(MARKER, 2024);
+        let marker_2025 = year_marker(MARKER, 2025);
+        let mut s = format!("{marker_2024}\n{marker_2024}\n{marker_2025}\n{marker_2025}");
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            2025,
+            &TableConfig::default(),
+            &Facts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(marker_2025, "<!--- benchmarking table 2025 --->");
+        assert!(s.starts_with(&format!(
+            "{marker_2024}\n{marker_2024}\n{marker_2025}\n## 2025 Benchmarks\n"
+        )));
+        assert!(s.ends_with(&format!("**Total: 190.00ms**\n{marker_2025}")));
+        assert!(!s.contains("## Benchmarks"));
+    }
+
+    #[test]
+    fn format_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(AllocStats {
+            allocations: 12,
+            bytes: 4096,
+            peak_bytes: 1536,
+        });
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            timings,
+            190.0,
+            2025,
+            &TableConfig::default(),
+            &Facts::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            s.contains("| Day | Parse | Part 1 | Part 2 | Part 1 Memory | Part 2 Memory |"),
+            true
+        );
+        assert_eq!(
+            s.contains(
+                "| [Day 1](./src/bin/2025-01.rs) | `-` | `10.0ms` | `20.0ms` | `1.5 KiB (12 allocs)` | `-` |"
+            ),
+            true
+        );
+    }
+
+    #[test]
+    fn format_failed_parts() {
+        assert_eq!(
+            format_cell(Some(Duration::from_millis(1)), Some(Status::Solved)),
+            "1.0ms"
+        );
+        assert_eq!(format_cell(None, Some(Status::TimedOut)), "✖ timed out");
+        assert_eq!(format_cell(None, Some(Status::Unsolved)), "-");
+        assert_eq!(format_cell(None, None), "-");
+    }
+
+    #[test]
+    fn format_shares() {
+        assert_eq!(format_share_cell(3.0, 12.0), "███░░░░░░░ 25.0%");
+        assert_eq!(format_share_cell(12.0, 12.0), "██████████ 100.0%");
+        assert_eq!(format_share_cell(0.0, 0.0), "░░░░░░░░░░ 0.0%");
+    }
+
+    #[test]
+    fn format_configured_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = stats(10_000_000, 120);
+        timings.data[0].part_2_stats = stats(20_000_000, 80);
+        timings.data[0].part_1_verdict = Some(Verdict::Correct);
+        timings.data[0].part_2_verdict = Some(Verdict::Correct);
+        timings.data[1].part_1_verdict = Some(Verdict::Correct);
+        timings.data[1].part_2_verdict = Some(Verdict::Incorrect {
+            expected: "2".into(),
+        });
+        timings.data[2].part_1_verdict = Some(Verdict::Correct);
+
+        let config = TableConfig {
+            columns: Some(vec![
+                Column::Part1,
+                Column::Samples,
+                Column::Share,
+                Column::Stars,
+                Column::Verified,
+            ]),
+            sort: Sort::Time,
+            ..TableConfig::default()
+        };
+        let facts = Facts {
+            total_nanos: 19e+10,
+            stars: BTreeMap::from([(day!(1).with_year(2025), 2), (day!(4).with_year(2025), 1)]),
+        };
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, 2025, &config, &facts).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 1 Samples | Part 2 Samples | Share | Stars | Verified |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 4](./src/bin/2025-04.rs) | `40.0ms` | `1` | `1` | █████░░░░░ 47.4% | ⭐ | - |",
+            "| [Day 2](./src/bin/2025-02.rs) | `30.0ms` | `1` | `1` | ████░░░░░░ 36.8% | - | - |",
+            "| [Day 1](./src/bin/2025-01.rs) | `10.0ms` | `120` | `80` | ██░░░░░░░░ 15.8% | ⭐⭐ | ✔ |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_html_table() {
+        let config = TableConfig {
+            columns: Some(vec![Column::Part1, Column::Part2]),
+            format: Format::Html,
+            year_heading: true,
+            ..TableConfig::default()
+        };
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            2025,
+            &config,
+            &Facts::default(),
+        )
+        .unwrap();
+        assert!(s.starts_with("<!--- benchmarking table --->\n## 2025 Benchmarks\n\n<table>\n"));
+        assert!(s.contains("    <tr><th>Day</th><th>Part 1</th><th>Part 2</th></tr>"));
+        assert!(s.contains(
+            "    <tr><td><a href=\"./src/bin/2025-02.rs\">Day 2</a></td><td><code>30.0ms</code></td><td><code>40.0ms</code></td></tr>"
+        ));
+        assert!(s.ends_with("</table>\n\n**Total: 190.00ms**\n<!--- benchmarking table --->"));
+    }
+
+    #[test]
+    fn reads_config_from_vars() {
+        let config = TableConfig::from_vars(|key| match key {
+            "AOC_README_COLUMNS" => Some("part_1, part_2,share".into()),
+            "AOC_README_SORT" => Some("time".into()),
+            "AOC_README_FORMAT" => Some("html".into()),
+            "AOC_README_YEAR_HEADING" => Some("true".into()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(
+            config,
+            TableConfig {
+                columns: Some(vec![Column::Part1, Column::Part2, Column::Share]),
+                sort: Sort::Time,
+                format: Format::Html,
+                year_heading: true,
+            }
+        );
+
+        assert_eq!(
+            TableConfig::from_vars(|_| None).unwrap(),
+            TableConfig::default()
+        );
+
+        let err = TableConfig::from_vars(|key| {
+            (key == "AOC_README_COLUMNS").then(|| "part_1,part_3".into())
+        })
+        .unwrap_err();
+        assert!(err.starts_with("unknown column `part_3`"));
+
+        let err = TableConfig::from_vars(|key| (key == "AOC_README_SORT").then(|| "name".into()))
+            .unwrap_err();
+        assert_eq!(err, "unknown sort order `name`, expected day or time.");
+    }
 }
@@ -0,0 +1,28 @@
+/// In-process registry of every solution in `src/bin`, used by `all` and `time`.
+///
+/// The list is generated by `build.rs`, which includes each day's binary as a module of the library.
+/// It is only populated when the `registry` feature is enabled, so that `solve` and the per-day
+/// tests do not have to compile every other day.
+use std::sync::Arc;
+
+use crate::template::{Day, protocol::PartRecord, runner::RunOptions};
+
+/// A solution as declared by the `solution!` macro.
+pub struct Solution {
+    pub day: Day,
+    /// Runs every part of the solution against the input, reporting results like the binary would.
+    /// Parts run on separate threads (see [`RunOptions::timeout`]), which share the input.
+    pub run: fn(Arc<str>, &RunOptions) -> Vec<PartRecord>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+/// Returns the solution for `day` if it is part of the registry.
+pub fn get(day: Day) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|solution| solution.day == day)
+}
+
+/// Whether this build was compiled with the `registry` feature.
+pub const fn is_enabled() -> bool {
+    cfg!(feature = "registry")
+}
@@ -1,63 +1,561 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
-use std::process::Output;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, process, thread};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::answers::{self, Answers, Verdict};
+use crate::template::aoc_client::{self, AocError, SubmitResult};
+use crate::template::memory::{self, AllocStats};
+use crate::template::protocol::{PartRecord, Phase, Status};
+use crate::template::submissions::{History, Submission};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, Part, now_unix};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Options that control how solution parts are run and reported.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench each part after its first run (`--time`).
+    pub time: bool,
+    /// Emit a [`PartRecord`] per part instead of human output (`--json`).
+    pub json: bool,
+    /// Wall-clock budget for every run of a part, including each bench iteration (`--timeout <seconds>`).
+    pub timeout: Option<Duration>,
+    /// How many iterations to bench.
+    pub bench: BenchConfig,
+    /// Only run the input with this name (`--input <name>`), see [`inputs`](crate::template::inputs).
+    pub input: Option<String>,
+}
+
+impl RunOptions {
+    /// Read the options from the arguments passed to a solution binary. Exits if they are invalid.
+    pub fn from_args() -> Self {
+        match Self::parse_args(&mut pico_args::Arguments::from_env()) {
+            Ok(options) => options,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Parse the options of a solution binary, the counterpart of [`Self::to_args`].
+    pub fn parse_args(args: &mut pico_args::Arguments) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            time: args.contains("--time"),
+            json: args.contains("--json"),
+            timeout: args.opt_value_from_fn("--timeout", parse_seconds)?,
+            bench: BenchConfig::parse_args(args)?,
+            input: args.opt_value_from_str("--input")?,
+        })
+    }
+
+    /// Arguments that pass these options on to a solution binary, the counterpart of [`Self::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.time {
+            args.push("--time".to_string());
+        }
+
+        if self.json {
+            args.push("--json".to_string());
+        }
+
+        if let Some(timeout) = self.timeout {
+            args.push("--timeout".to_string());
+            args.push(timeout.as_secs_f64().to_string());
+        }
+
+        if let Some(input) = &self.input {
+            args.push("--input".to_string());
+            args.push(input.clone());
+        }
+
+        args.extend(self.bench.to_args());
+        args
+    }
+}
+
+/// Controls how many times a part is run when benching.
+///
+/// By default, the number of iterations is derived from the duration of the first run,
+/// so that benching takes roughly `budget`, clamped to `min_iterations..=max_iterations`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate time to spend benching a part (`--budget <seconds>`).
+    pub budget: Duration,
+    /// Lower bound of the number of iterations (`--min-iters <n>`).
+    pub min_iterations: u128,
+    /// Upper bound of the number of iterations (`--max-iters <n>`).
+    pub max_iterations: u128,
+    /// Run exactly this many iterations, ignoring budget and bounds (`--iters <n>`).
+    pub iterations: Option<u128>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_iterations: 10,
+            max_iterations: 10000,
+            iterations: None,
+        }
+    }
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+impl BenchConfig {
+    /// Parse the bench flags of `time` and of solution binaries, see [`Self::to_args`].
+    pub fn parse_args(args: &mut pico_args::Arguments) -> Result<Self, Box<dyn std::error::Error>> {
+        let defaults = Self::default();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+        let config = Self {
+            budget: args
+                .opt_value_from_fn("--budget", parse_seconds)?
+                .unwrap_or(defaults.budget),
+            min_iterations: args
+                .opt_value_from_str("--min-iters")?
+                .unwrap_or(defaults.min_iterations),
+            max_iterations: args
+                .opt_value_from_str("--max-iters")?
+                .unwrap_or(defaults.max_iterations),
+            iterations: args.opt_value_from_str("--iters")?,
+        };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        config.validate()?;
+        Ok(config)
     }
+
+    /// Checks that the configured iterations can be run.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_iterations == 0 {
+            return Err("`--min-iters` must be at least 1.".into());
+        }
+
+        if self.min_iterations > self.max_iterations {
+            return Err("`--min-iters` must not be larger than `--max-iters`.".into());
+        }
+
+        if self.iterations == Some(0) {
+            return Err("`--iters` must be at least 1.".into());
+        }
+
+        Ok(())
+    }
+
+    /// Number of iterations to bench a part whose first run took `base_time`.
+    pub fn iterations_for(&self, base_time: &Duration) -> u128 {
+        let iterations = self.iterations.unwrap_or_else(|| {
+            (self.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+                .min(self.max_iterations)
+                .max(self.min_iterations)
+        });
+
+        cmp::max(iterations, 1)
+    }
+
+    /// Arguments that pass this configuration on to a solution binary. Defaults are omitted.
+    pub fn to_args(&self) -> Vec<String> {
+        let defaults = Self::default();
+        let mut args = vec![];
+
+        if self.budget != defaults.budget {
+            args.extend([
+                "--budget".to_string(),
+                self.budget.as_secs_f64().to_string(),
+            ]);
+        }
+
+        if self.min_iterations != defaults.min_iterations {
+            args.extend(["--min-iters".to_string(), self.min_iterations.to_string()]);
+        }
+
+        if self.max_iterations != defaults.max_iterations {
+            args.extend(["--max-iters".to_string(), self.max_iterations.to_string()]);
+        }
+
+        if let Some(iterations) = self.iterations {
+            args.extend(["--iters".to_string(), iterations.to_string()]);
+        }
+
+        args
+    }
+}
+
+/// Parses a non-negative number of seconds, e.g. the value of `--timeout`.
+pub fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let seconds: f64 = s
+        .parse()
+        .map_err(|_| format!("invalid number of seconds: {s}"))?;
+    Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string())
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
-    input: I,
+/// Reason why a part did not produce a result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The part panicked, with the panic message if there was one.
+    Panicked(String),
+    /// The part did not finish within the configured budget.
+    TimedOut(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panicked(message) => write!(f, "panicked: {message}"),
+            Failure::TimedOut(budget) => write!(f, "timed out after {budget:.1?}"),
+        }
+    }
+}
+
+pub fn run_part<I, T>(
+    func: impl Fn(&I) -> Option<T> + Copy + Send + 'static,
+    input: Arc<I>,
+    day: Day,
+    part: Part,
+    options: &RunOptions,
+) -> PartRecord
+where
+    I: ?Sized + Send + Sync + 'static,
+    T: Display + Send + 'static,
+{
+    let phase = Phase::Part(part);
+    let label = phase.label();
+
+    let outcome = run_timed(func, input, options, |result| {
+        print_result(result, &label, "");
+    });
+
+    let (result, measurement) = match outcome {
+        Ok(outcome) => outcome,
+        Err(failure) => {
+            let record = PartRecord::failed(phase, &failure);
+            if options.json {
+                println!("{}", record.to_json_line());
+            } else {
+                print_failure(&label, &failure);
+            }
+            return record;
+        }
+    };
+
+    let answer = result.as_ref().map(ToString::to_string);
+    let mut record = PartRecord::new(phase, answer, measurement.duration, measurement.stats);
+    record.memory = measurement.memory;
+    record.timed = measurement.timed;
+
+    if let Some(answer) = &record.answer {
+        record.verdict = Answers::read(day, options.input.as_deref()).check(part, answer);
+    }
+
+    if options.json {
+        println!("{}", record.to_json_line());
+    } else {
+        print_result(
+            &result,
+            &label,
+            &format!(
+                "{}{}",
+                measurement.format(),
+                format_verdict(&record.verdict)
+            ),
+        );
+    }
+
+    record
+}
+
+/// Print a record emitted by a solution binary (`--json`) the way the binary would have printed it.
+pub fn print_record(record: &PartRecord) {
+    let label = record.label();
+    let error = record.error.as_deref().unwrap_or_default();
+
+    match record.status {
+        Status::Panicked => println!("{label}: ✖ panicked: {error}"),
+        Status::TimedOut => println!("{label}: ✖ {error}"),
+        Status::Solved | Status::Unsolved => {
+            let measurement = Measurement {
+                duration: record.duration(),
+                stats: record.stats,
+                memory: record.memory,
+                timed: record.timed,
+            };
+
+            if record.part == Phase::Parse {
+                print_result(&Some("✔"), &label, &measurement.format());
+            } else {
+                print_result(
+                    &record.answer,
+                    &label,
+                    &format!(
+                        "{}{}",
+                        measurement.format(),
+                        format_verdict(&record.verdict)
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Run the parse function declared via `solution!(day, parse = ...)` and return its output,
+/// which is then shared by all parts. Timed separately from the parts.
+///
+/// Returns `None` as output if parsing failed, in which case the parts should not be run.
+pub fn run_parse<I, T>(
+    func: impl Fn(&I) -> T + Copy + Send + 'static,
+    input: Arc<I>,
+    options: &RunOptions,
+) -> (Option<T>, PartRecord)
+where
+    I: ?Sized + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    let label = Phase::Parse.label();
+    let done = Some("✔");
+
+    let outcome = run_timed(func, input, options, |_| {
+        print_result(&done, &label, "");
+    });
+
+    let (result, measurement) = match outcome {
+        Ok(outcome) => outcome,
+        Err(failure) => {
+            let record = PartRecord::failed(Phase::Parse, &failure);
+            if options.json {
+                println!("{}", record.to_json_line());
+            } else {
+                print_failure(&label, &failure);
+            }
+            return (None, record);
+        }
+    };
+
+    let mut record = PartRecord::new(Phase::Parse, None, measurement.duration, measurement.stats);
+    record.memory = measurement.memory;
+    record.timed = measurement.timed;
+
+    if options.json {
+        println!("{}", record.to_json_line());
+    } else {
+        print_result(&done, &label, &measurement.format());
+    }
+
+    (Some(result), record)
+}
+
+/// Timing and allocations of a part that ran to completion.
+struct Measurement {
+    duration: Duration,
+    stats: Option<BenchStats>,
+    memory: Option<AllocStats>,
+    /// Whether `duration` is meaningful, see [`RUNNING_PARTS`].
+    timed: bool,
+}
+
+impl Measurement {
+    fn format(&self) -> String {
+        let duration = if self.timed {
+            format_duration(&self.duration, self.stats.as_ref())
+        } else {
+            " (not timed, a part that timed out is still running)".into()
+        };
+        format!(
+            "{duration}{}",
+            self.memory.as_ref().map_or(String::new(), format_memory)
+        )
+    }
+}
+
+/// Number of threads that run a part, including parts that timed out and keep running in the background.
+/// While such a part is running, it competes with later parts for the CPU and skews the allocation counters,
+/// so later parts are not timed.
+static RUNNING_PARTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Marks a thread as running a part until dropped.
+struct RunningPart;
+
+impl RunningPart {
+    fn start() -> Self {
+        RUNNING_PARTS.fetch_add(1, Ordering::SeqCst);
+        RunningPart
+    }
+}
+
+impl Drop for RunningPart {
+    fn drop(&mut self) {
+        RUNNING_PARTS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Messages from the thread that runs a part.
+enum Message<T> {
+    /// The result of the first run, its duration and allocations.
+    Done(T, Duration, Option<AllocStats>),
+    /// A bench iteration finished.
+    Sample,
+    /// Benching finished.
+    Benched(BenchStats),
+}
+
+/// Stack size of the thread that runs a part. Generous, since solutions tend to recurse deeply.
+const PART_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+/// Run a solution part. The behavior differs depending on whether we are benching (`--time`):
+///  1. by default, the function is executed once.
+///  2. when benching, the function is additionally run as configured by [`BenchConfig`]
+///     (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// All executions happen on a separate thread, so that a panic or an exceeded
+/// [`RunOptions::timeout`] is reported as a [`Failure`] instead of aborting the run.
+/// The timeout applies to every execution, including each bench iteration.
+/// With the `count-allocs` feature, the allocations of the first execution are recorded.
+/// NOTE: a part that timed out cannot be stopped. It stops benching, but an execution that hangs
+/// keeps running in the background and later parts are not timed, see [`RUNNING_PARTS`].
+fn run_timed<I, T>(
+    func: impl Fn(&I) -> T + Copy + Send + 'static,
+    input: Arc<I>,
+    options: &RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+) -> Result<(T, Measurement), Failure>
+where
+    I: ?Sized + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    let timed = RUNNING_PARTS.load(Ordering::SeqCst) == 0;
+    let bench_config = (options.time && timed).then_some(options.bench);
+
+    let (sender, receiver) = mpsc::channel();
+    let running = RunningPart::start();
+
+    let handle = thread::Builder::new()
+        .stack_size(PART_STACK_SIZE)
+        .spawn(move || {
+            let _running = running;
+
+            let snapshot = memory::start();
+            let timer = Instant::now();
+            let result = {
+                #[cfg(feature = "dhat-heap")]
+                let _profiler = dhat::Profiler::new_heap();
+
+                func(&input)
+            };
+            let elapsed = timer.elapsed();
+            let memory = snapshot.as_ref().map(memory::finish);
+            if sender.send(Message::Done(result, elapsed, memory)).is_err() {
+                return;
+            }
+
+            if let Some(config) = bench_config {
+                // the receiver is gone once the part timed out, stop benching then.
+                let stats = bench(func, &input, &elapsed, &config, || {
+                    sender.send(Message::Sample).is_ok()
+                });
+                if let Some(stats) = stats {
+                    let _ = sender.send(Message::Benched(stats));
+                }
+            }
+        })
+        .expect("failed to spawn thread for solution part");
+
+    let mut handle = Some(handle);
+    let mut receive = || {
+        let received = match options.timeout {
+            Some(timeout) => receiver.recv_timeout(timeout),
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        received.map_err(|e| match e {
+            RecvTimeoutError::Timeout => Failure::TimedOut(options.timeout.unwrap_or_default()),
+            RecvTimeoutError::Disconnected => {
+                let payload = handle
+                    .take()
+                    .expect("thread should only be joined once")
+                    .join()
+                    .expect_err("thread exited without a result");
+                Failure::Panicked(panic_message(payload.as_ref()))
+            }
+        })
+    };
 
-        func(input)
+    let Message::Done(result, base_time, memory) = receive()? else {
+        unreachable!("the first message of a part is its result");
     };
-    let base_time = timer.elapsed();
 
-    hook(&result);
+    if !options.json {
+        hook(&result);
+    }
+
+    let measurement = if bench_config.is_some() {
+        if !options.json {
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+        let stats = loop {
+            match receive()? {
+                Message::Sample => {}
+                Message::Benched(stats) => break stats,
+                Message::Done(..) => unreachable!("a part only sends its result once"),
+            }
+        };
+
+        Measurement {
+            duration: stats.mean,
+            stats: Some(stats),
+            memory,
+            timed,
+        }
     } else {
-        (base_time, 1)
+        Measurement {
+            duration: if timed { base_time } else { Duration::ZERO },
+            stats: None,
+            memory,
+            timed,
+        }
     };
 
-    (result, run.0, run.1)
+    // the thread exits right after its last message, wait for it so that it no longer counts as running.
+    if let Some(handle) = handle.take() {
+        let _ = handle.join();
+    }
+
+    Ok((result, measurement))
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".into()
+    }
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Bench `func`, calling `tick` after every iteration. Stops early and returns `None` once `tick` returns `false`.
+fn bench<I: ?Sized, T>(
+    func: impl Fn(&I) -> T,
+    input: &I,
+    base_time: &Duration,
+    config: &BenchConfig,
+    tick: impl Fn() -> bool,
+) -> Option<BenchStats> {
+    let bench_iterations = config.iterations_for(base_time);
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    // warm up caches and branch predictors before any sample is recorded.
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input)));
+        if !tick() {
+            return None;
+        }
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -65,31 +563,127 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
+        if !tick() {
+            return None;
+        }
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Some(BenchStats::from_samples(&timers))
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Summary of the samples collected while benching a solution part.
+///
+/// Samples outside the Tukey fences (1.5 × IQR beyond the quartiles) are rejected as outliers
+/// before `mean`, `std_dev` and the percentiles are computed. `samples` counts every timed run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub samples: u128,
+    pub outliers: u128,
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+impl BenchStats {
+    /// Computes statistics over a non-empty set of samples.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let q1 = as_nanos_f64(percentile(&sorted, 25.0));
+        let q3 = as_nanos_f64(percentile(&sorted, 75.0));
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let kept: Vec<Duration> = sorted
+            .iter()
+            .copied()
+            .filter(|d| (lower..=upper).contains(&as_nanos_f64(*d)))
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = kept.len() as f64;
+        let mean = kept.iter().copied().map(as_nanos_f64).sum::<f64>() / len;
+        let variance = kept
+            .iter()
+            .map(|d| (as_nanos_f64(*d) - mean).powi(2))
+            .sum::<f64>()
+            / len;
+
+        Self {
+            mean: from_nanos_f64(mean),
+            median: percentile(&kept, 50.0),
+            min: kept[0],
+            max: kept[kept.len() - 1],
+            std_dev: from_nanos_f64(variance.sqrt()),
+            p95: percentile(&kept, 95.0),
+            p99: percentile(&kept, 99.0),
+            samples: samples.len() as u128,
+            outliers: (samples.len() - kept.len()) as u128,
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn as_nanos_f64(duration: Duration) -> f64 {
+    duration.as_nanos() as f64
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos_f64(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/// Nearest-rank percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn format_duration(duration: &Duration, stats: Option<&BenchStats>) -> String {
+    match stats {
+        None => format!(" ({duration:.1?})"),
+        Some(s) => format!(
+            " ({duration:.1?} @ {} samples) [median {:.1?}, min {:.1?}, max {:.1?}, σ {:.1?}, p95 {:.1?}, p99 {:.1?}, outliers {}]",
+            s.samples, s.median, s.min, s.max, s.std_dev, s.p95, s.p99, s.outliers
+        ),
+    }
+}
+
+fn format_memory(memory: &AllocStats) -> String {
+    format!(
+        " [{} allocs, {} allocated, {} peak]",
+        memory.allocations,
+        memory::format_bytes(memory.bytes),
+        memory::format_bytes(memory.peak_bytes)
+    )
+}
+
+fn format_verdict(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Correct => " ✔".into(),
+        Verdict::Incorrect { expected } if !expected.contains('\n') => {
+            format!(" ✘ (expected {expected})")
+        }
+        Verdict::Incorrect { .. } => " ✘".into(),
+        Verdict::Unknown => String::new(),
     }
 }
 
+fn print_failure(part: &str, failure: &Failure) {
+    print!("\r");
+    println!("{part}: ✖ {failure}");
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -125,43 +719,165 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Submit the answer of a part unless the submission history refuses it,
+/// then record the reply and store correct answers as known answers.
+pub fn submit_answer(day: Day, part: Part, answer: &str) -> Option<Result<SubmitResult, AocError>> {
+    let mut history = match History::read() {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Not submitting {answer}: {e}");
+            return None;
+        }
+    };
 
-    if !args.contains(&"--submit".into()) {
+    if let Err(refusal) = history.check(day, part, answer, now_unix()) {
+        eprintln!("Not submitting {answer}: {refusal}");
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    println!("Submitting result...");
+    let timestamp = now_unix();
+    let result = aoc_client::submit(day, part, answer);
+
+    match &result {
+        Ok(result) => {
+            println!("{result}");
+
+            let submission = Submission {
+                day,
+                part,
+                answer: answer.into(),
+                result: result.clone(),
+                timestamp,
+            };
+
+            if let Err(e) = history.record(submission) {
+                eprintln!("Failed to record submission: {e}");
+            }
+        }
+        Err(e) => eprintln!("Failed to submit: {e}"),
+    }
+
+    if matches!(result, Ok(SubmitResult::Correct)) {
+        match answers::accept(day, None, part, answer) {
+            Ok(()) => println!("Stored answer in \"{}\".", Answers::path(day, None)),
+            Err(e) => eprintln!("Failed to store answer: {e}"),
+        }
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    Some(result)
+}
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchConfig, BenchStats, RunOptions};
+    use std::time::Duration;
 
-    if part_submit != part {
-        return None;
+    fn args(values: &[&str]) -> pico_args::Arguments {
+        pico_args::Arguments::from_vec(values.iter().map(Into::into).collect())
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn computes_stats_for_uniform_samples() {
+        let stats = BenchStats::from_samples(&nanos(&[10, 20, 30, 40, 50]));
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.p95, Duration::from_nanos(50));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.std_dev.as_nanos(), 14);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = BenchStats::from_samples(&nanos(&[100, 101, 99, 100, 102, 98, 100, 5000]));
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_nanos(102));
+        assert_eq!(stats.mean, Duration::from_nanos(100));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn derives_iterations_from_budget() {
+        let config = BenchConfig::default();
+        assert_eq!(config.iterations_for(&Duration::from_millis(10)), 100);
+        assert_eq!(config.iterations_for(&Duration::from_secs(5)), 10);
+        assert_eq!(config.iterations_for(&Duration::from_nanos(1)), 10000);
+
+        let config = BenchConfig {
+            budget: Duration::from_secs(5),
+            min_iterations: 2,
+            max_iterations: 1_000_000,
+            iterations: None,
+        };
+        assert_eq!(config.iterations_for(&Duration::from_secs(10)), 2);
+        assert_eq!(config.iterations_for(&Duration::from_nanos(1)), 1_000_000);
+    }
+
+    #[test]
+    fn uses_fixed_iterations() {
+        let config = BenchConfig {
+            iterations: Some(3),
+            ..BenchConfig::default()
+        };
+        assert_eq!(config.iterations_for(&Duration::from_nanos(1)), 3);
+        assert_eq!(config.to_args(), vec!["--iters", "3"]);
+        assert_eq!(BenchConfig::default().to_args(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn parses_run_options() {
+        let options = RunOptions {
+            time: true,
+            json: true,
+            timeout: Some(Duration::from_millis(1500)),
+            bench: BenchConfig {
+                budget: Duration::from_secs(2),
+                min_iterations: 5,
+                max_iterations: 50,
+                iterations: None,
+            },
+            input: Some("alt".into()),
+        };
+        let to_args = options.to_args();
+        let to_args: Vec<&str> = to_args.iter().map(String::as_str).collect();
+
+        let parsed = RunOptions::parse_args(&mut args(&to_args)).unwrap();
+        assert_eq!(parsed.time, true);
+        assert_eq!(parsed.json, true);
+        assert_eq!(parsed.timeout, options.timeout);
+        assert_eq!(parsed.bench, options.bench);
+        assert_eq!(parsed.input, options.input);
+    }
+
+    #[test]
+    fn rejects_invalid_bench_flags() {
+        for invalid in [
+            &["--iters", "abc"][..],
+            &["--budget", "-1"],
+            &["--timeout", "soon"],
+            &["--min-iters", "0"],
+            &["--iters", "0"],
+            &["--min-iters", "500", "--max-iters", "10"],
+        ] {
+            assert!(
+                RunOptions::parse_args(&mut args(invalid)).is_err(),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.mean, Duration::from_nanos(42));
+        assert_eq!(stats.p99, Duration::from_nanos(42));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
 }
@@ -0,0 +1,215 @@
+/// Plans and writes the files of a day created by `cargo scaffold` and `cargo reset`.
+///
+/// Every target is checked before anything is written, so existing files can be reported
+/// up front and are only replaced when asked to.
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::template::{Day, data_dir};
+
+/// A file created for a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Target {
+    pub kind: Kind,
+    pub path: String,
+    pub content: String,
+    pub exists: bool,
+    /// Whether an existing file is replaced.
+    pub overwrite: bool,
+}
+
+impl Target {
+    /// Whether the target is written, i.e. it's missing or replaced.
+    pub fn is_written(&self) -> bool {
+        !self.exists || self.overwrite
+    }
+
+    /// Path of the copy of this target in a backup directory, see [`backup`].
+    fn backup_path(&self, backup_dir: &str) -> String {
+        let name = Path::new(&self.path).file_name().unwrap().to_string_lossy();
+        match self.kind {
+            Kind::Module => format!("{backup_dir}/{name}"),
+            Kind::Input => format!("{backup_dir}/inputs/{name}"),
+            Kind::Example => format!("{backup_dir}/examples/{name}"),
+        }
+    }
+}
+
+/// The files created for a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Module,
+    Input,
+    Example,
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Kind::Module => "module file",
+            Kind::Input => "input file",
+            Kind::Example => "example file",
+        })
+    }
+}
+
+/// Which existing targets are replaced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overwrite {
+    /// Only missing files are created.
+    None,
+    /// The module file is replaced, the input and example are only created if missing.
+    Module,
+    /// All files are replaced.
+    All,
+}
+
+/// Lists the module, input and example file of `day`, with the module rendered as `module`.
+pub fn plan(day: Day, module: String, overwrite: Overwrite) -> Vec<Target> {
+    plan_with(day, module, overwrite, |path| Path::new(path).exists())
+}
+
+fn plan_with(
+    day: Day,
+    module: String,
+    overwrite: Overwrite,
+    exists: impl Fn(&str) -> bool,
+) -> Vec<Target> {
+    let dir = data_dir(day.year());
+
+    [
+        (
+            Kind::Module,
+            format!("src/bin/{}.rs", day.bin_name()),
+            module,
+        ),
+        (
+            Kind::Input,
+            format!("{dir}/inputs/{day}.txt"),
+            String::new(),
+        ),
+        (
+            Kind::Example,
+            format!("{dir}/examples/{day}.txt"),
+            String::new(),
+        ),
+    ]
+    .into_iter()
+    .map(|(kind, path, content)| Target {
+        kind,
+        exists: exists(&path),
+        overwrite: match overwrite {
+            Overwrite::None => false,
+            Overwrite::Module => kind == Kind::Module,
+            Overwrite::All => true,
+        },
+        path,
+        content,
+    })
+    .collect()
+}
+
+/// Writes all targets that are missing or replaced.
+pub fn write(targets: &[Target]) -> Result<(), io::Error> {
+    for target in targets.iter().filter(|target| target.is_written()) {
+        if let Some(parent) = Path::new(&target.path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target.path, &target.content)?;
+    }
+    Ok(())
+}
+
+/// Directory that `backup` copies the files of `day` to, e.g. `data/2025/backups/05-1733029200`.
+pub fn backup_dir(day: Day, timestamp: u64) -> String {
+    format!("{}/backups/{day}-{timestamp}", data_dir(day.year()))
+}
+
+/// Copies the existing targets to `backup_dir` and returns the paths of the copies.
+pub fn backup(targets: &[Target], backup_dir: &str) -> Result<Vec<String>, io::Error> {
+    let mut copies = vec![];
+
+    for target in targets.iter().filter(|target| target.exists) {
+        let copy = target.backup_path(backup_dir);
+        if let Some(parent) = Path::new(&copy).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&target.path, &copy)?;
+        copies.push(copy);
+    }
+
+    Ok(copies)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Kind, Overwrite, Target, backup_dir, plan_with};
+    use crate::template::Day;
+
+    fn day() -> Day {
+        Day::of_year(2024, 5).unwrap()
+    }
+
+    fn written(targets: &[Target]) -> Vec<Kind> {
+        targets
+            .iter()
+            .filter(|target| target.is_written())
+            .map(|target| target.kind)
+            .collect()
+    }
+
+    #[test]
+    fn plans_missing_files() {
+        let targets = plan_with(day(), "module".into(), Overwrite::None, |_| false);
+        let paths: Vec<&str> = targets.iter().map(|target| target.path.as_str()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "src/bin/2024-05.rs",
+                "data/2024/inputs/05.txt",
+                "data/2024/examples/05.txt"
+            ]
+        );
+        assert_eq!(targets[0].content, "module");
+        assert_eq!(written(&targets).len(), 3);
+    }
+
+    #[test]
+    fn keeps_existing_files() {
+        let exists = |path: &str| path.contains("inputs") || path.ends_with(".rs");
+
+        let targets = plan_with(day(), String::new(), Overwrite::None, exists);
+        assert_eq!(written(&targets), vec![Kind::Example]);
+
+        let targets = plan_with(day(), String::new(), Overwrite::Module, exists);
+        assert_eq!(written(&targets), vec![Kind::Module, Kind::Example]);
+
+        let targets = plan_with(day(), String::new(), Overwrite::All, exists);
+        assert_eq!(written(&targets).len(), 3);
+    }
+
+    #[test]
+    fn places_backups() {
+        let dir = backup_dir(day(), 1733029200);
+        assert_eq!(dir, "data/2024/backups/05-1733029200");
+
+        let targets = plan_with(day(), String::new(), Overwrite::All, |_| true);
+        let copies: Vec<String> = targets
+            .iter()
+            .map(|target| target.backup_path(&dir))
+            .collect();
+        assert_eq!(
+            copies,
+            vec![
+                "data/2024/backups/05-1733029200/2024-05.rs",
+                "data/2024/backups/05-1733029200/inputs/05.txt",
+                "data/2024/backups/05-1733029200/examples/05.txt"
+            ]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::template::{DEFAULT_YEAR, Day, DayFromStrError, all_days, progress::Progress};
+
+/// A selection of days of a year, e.g. from command-line arguments.
+///
+/// A selector is a comma-separated list of:
+///  - a day, e.g. `4`.
+///  - a range of days, e.g. `3..7`. Both ends are inclusive and can be omitted, e.g. `..5` or `20..`.
+///  - `all`, `odd` or `even` days of the calendar.
+///  - `unsolved`: days with less than two stars, see [`Progress`].
+///  - `last`: the most recent day with a solution in `src/bin`.
+///
+/// ```
+/// # use advent_of_code::template::DaySelector;
+/// let selector = DaySelector::parse("1,4..6", 2024).unwrap();
+/// let days: Vec<u8> = selector.days().into_iter().map(|day| day.into_inner()).collect();
+/// assert_eq!(days, vec![1, 4, 5, 6]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DaySelector {
+    year: u16,
+    source: String,
+    items: Vec<Selection>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Selection {
+    /// Days from the first to the second, both inclusive.
+    Range(Day, Day),
+    All,
+    Odd,
+    Even,
+    Unsolved,
+    Last,
+}
+
+impl DaySelector {
+    /// Selects every day of the calendar of `year`.
+    pub fn all(year: u16) -> Self {
+        Self {
+            year,
+            source: "all".into(),
+            items: vec![Selection::All],
+        }
+    }
+
+    /// Parses a selector of days of `year`.
+    pub fn parse(s: &str, year: u16) -> Result<Self, DaySelectorError> {
+        let items = s
+            .split(',')
+            .map(|item| parse_item(item.trim(), year))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            year,
+            source: s.into(),
+            items,
+        })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Resolves the selected days in ascending order.
+    ///
+    /// Stars and solutions are only looked up if the selector contains `unsolved` or `last`.
+    pub fn days(&self) -> Vec<Day> {
+        let progress = self.uses(Selection::Unsolved).then(|| {
+            Progress::read(self.year).unwrap_or_else(|e| {
+                eprintln!("Ignoring stars: {e}");
+                Progress::new(self.year)
+            })
+        });
+
+        let solutions: Vec<Day> = if self.uses(Selection::Last) {
+            all_days(self.year)
+                .filter(|day| Path::new(&format!("src/bin/{}.rs", day.bin_name())).exists())
+                .collect()
+        } else {
+            vec![]
+        };
+
+        self.select(
+            |day| {
+                progress
+                    .as_ref()
+                    .is_some_and(|progress| progress.get(day) >= 2)
+            },
+            &solutions,
+        )
+    }
+
+    fn uses(&self, selection: Selection) -> bool {
+        self.items.contains(&selection)
+    }
+
+    fn select(&self, is_solved: impl Fn(Day) -> bool, solutions: &[Day]) -> Vec<Day> {
+        all_days(self.year)
+            .filter(|&day| {
+                self.items.iter().any(|item| match *item {
+                    Selection::Range(start, end) => day >= start && day <= end,
+                    Selection::All => true,
+                    Selection::Odd => day.into_inner() % 2 == 1,
+                    Selection::Even => day.into_inner() % 2 == 0,
+                    Selection::Unsolved => !is_solved(day),
+                    Selection::Last => solutions.last() == Some(&day),
+                })
+            })
+            .collect()
+    }
+}
+
+fn parse_item(item: &str, year: u16) -> Result<Selection, DaySelectorError> {
+    let selection = match item {
+        "all" => Selection::All,
+        "odd" => Selection::Odd,
+        "even" => Selection::Even,
+        "unsolved" => Selection::Unsolved,
+        "last" => Selection::Last,
+        _ => match item.split_once("..") {
+            Some((start, end)) => {
+                let end = end.strip_prefix('=').unwrap_or(end);
+                let day_or = |s: &str, default: Option<Day>| match s {
+                    "" => default.ok_or_else(|| DaySelectorError::InvalidRange(item.into())),
+                    s => Day::parse(s, year).map_err(DaySelectorError::InvalidDay),
+                };
+
+                let start = day_or(start, all_days(year).next())?;
+                let end = day_or(end, all_days(year).last())?;
+                if start > end {
+                    return Err(DaySelectorError::InvalidRange(item.into()));
+                }
+                Selection::Range(start, end)
+            }
+            None => {
+                let day = Day::parse(item, year).map_err(DaySelectorError::InvalidDay)?;
+                Selection::Range(day, day)
+            }
+        },
+    };
+
+    Ok(selection)
+}
+
+impl Display for DaySelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySelector {
+    type Err = DaySelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, DEFAULT_YEAR)
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelector`].
+#[derive(Debug)]
+pub enum DaySelectorError {
+    InvalidDay(DayFromStrError),
+    /// A range whose first day is after its last day, e.g. `7..3`.
+    InvalidRange(String),
+}
+
+impl Error for DaySelectorError {}
+
+impl Display for DaySelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaySelectorError::InvalidDay(e) => write!(
+                f,
+                "{e}, a range like `3..7`, a list like `1,4,9`, or one of `all`, `odd`, `even`, `unsolved` and `last`"
+            ),
+            DaySelectorError::InvalidRange(range) => write!(
+                f,
+                "invalid range `{range}`, expecting the first day to not be after the last day"
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DaySelector, Selection};
+    use crate::template::Day;
+
+    fn day(day: u8) -> Day {
+        Day::of_year(2024, day).unwrap()
+    }
+
+    fn select(s: &str, solved: &[u8], solutions: &[u8]) -> Vec<u8> {
+        let solutions: Vec<Day> = solutions.iter().map(|&x| day(x)).collect();
+        DaySelector::parse(s, 2024)
+            .unwrap()
+            .select(|d| solved.contains(&d.into_inner()), &solutions)
+            .into_iter()
+            .map(Day::into_inner)
+            .collect()
+    }
+
+    #[test]
+    fn selects_days() {
+        assert_eq!(select("4", &[], &[]), vec![4]);
+        assert_eq!(select("3..7", &[], &[]), vec![3, 4, 5, 6, 7]);
+        assert_eq!(select("3..=5", &[], &[]), vec![3, 4, 5]);
+        assert_eq!(select("..3", &[], &[]), vec![1, 2, 3]);
+        assert_eq!(select("23..", &[], &[]), vec![23, 24, 25]);
+        assert_eq!(select("9,1,4,4", &[], &[]), vec![1, 4, 9]);
+        assert_eq!(select("1, 20..21", &[], &[]), vec![1, 20, 21]);
+        assert_eq!(select("all", &[], &[]).len(), 25);
+        assert_eq!(select("odd", &[], &[]).len(), 13);
+        assert_eq!(select("even", &[], &[])[..2], [2, 4]);
+        assert_eq!(
+            select("unsolved,..3", &(2..=25).collect::<Vec<_>>(), &[]),
+            vec![1, 2, 3]
+        );
+        assert_eq!(select("last", &[], &[1, 2, 5]), vec![5]);
+        assert_eq!(select("last", &[], &[]), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn selects_days_of_year() {
+        let days = DaySelector::all(2025).select(|_| false, &[]);
+        assert_eq!(days.len(), 12);
+        assert!(days.iter().all(|day| day.year() == 2025));
+    }
+
+    #[test]
+    fn only_uses_stars_and_solutions_when_selected() {
+        let uses = |s: &str, selection| DaySelector::parse(s, 2024).unwrap().uses(selection);
+
+        assert!(!uses("1..5,9", Selection::Unsolved));
+        assert!(!uses("all", Selection::Last));
+        assert!(uses("unsolved", Selection::Unsolved));
+        assert!(!uses("unsolved", Selection::Last));
+        assert!(uses("1,last", Selection::Last));
+    }
+
+    #[test]
+    fn rejects_invalid_selectors() {
+        let error = |s: &str, year| DaySelector::parse(s, year).unwrap_err().to_string();
+
+        assert_eq!(
+            error("7..3", 2024),
+            "invalid range `7..3`, expecting the first day to not be after the last day"
+        );
+        assert!(
+            error("1..13", 2025).starts_with("expecting a day number between 1 and 12 for 2025")
+        );
+        assert!(error("1,x", 2024).starts_with("expecting a day number between 1 and 25 for 2024"));
+        assert!(DaySelector::parse("..", 2024).is_ok());
+    }
+}
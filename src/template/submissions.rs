@@ -0,0 +1,496 @@
+/// Local history of submitted answers of every year, stored in `data/submissions.json`.
+///
+/// The history guards against wasted attempts: answers that were already rejected or that fall
+/// outside the bounds of earlier "too high" / "too low" replies are not submitted again,
+/// and no answer is submitted while a wait time from an earlier reply has not expired.
+use std::{collections::HashMap, fmt::Display, fs, io, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    DEFAULT_YEAR, Day, Part,
+    aoc_client::{Hint, SubmitResult},
+};
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// A submitted answer and the reply of the website.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: Part,
+    pub answer: String,
+    pub result: SubmitResult,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Submission {
+    /// Point in time until which no answer should be submitted, if the reply asked to wait.
+    fn wait_until(&self) -> Option<u64> {
+        match &self.result {
+            SubmitResult::Incorrect { wait, .. } | SubmitResult::TooRecent { wait } => {
+                wait.map(|wait| self.timestamp + wait.as_secs())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Reason why an answer is not submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved with this answer.
+    AlreadySolved { answer: String },
+    /// This exact answer was already rejected.
+    AlreadyRejected,
+    /// The answer is not below an answer that was too high, or not above one that was too low.
+    OutOfBounds { hint: Hint, bound: String },
+    /// An earlier reply asked to wait.
+    Cooldown { remaining: Duration },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::AlreadyRejected => write!(f, "this answer was already rejected."),
+            Refusal::OutOfBounds {
+                hint: Hint::TooHigh,
+                bound,
+            } => write!(f, "{bound} was already too high."),
+            Refusal::OutOfBounds {
+                hint: Hint::TooLow,
+                bound,
+            } => write!(f, "{bound} was already too low."),
+            Refusal::Cooldown { remaining } => {
+                write!(f, "wait {remaining:?} before submitting again.")
+            }
+        }
+    }
+}
+
+/// Every submission made from this repository.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct History {
+    pub submissions: Vec<Submission>,
+}
+
+impl History {
+    /// Read the history. If not present, returns an empty history.
+    ///
+    /// Fails if the file exists but cannot be read or parsed. Without the history, answers could be
+    /// submitted during a cooldown or twice, and storing a new submission would erase it.
+    pub fn read() -> Result<Self, String> {
+        match fs::read_to_string(SUBMISSIONS_FILE_PATH) {
+            Ok(s) => History::try_from(s).map_err(|e| {
+                format!("\"{SUBMISSIONS_FILE_PATH}\" is corrupt, please fix or remove it: {e}")
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("failed to read \"{SUBMISSIONS_FILE_PATH}\": {e}")),
+        }
+    }
+
+    pub fn store(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(SUBMISSIONS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Add a submission to the history and store it.
+    pub fn record(&mut self, submission: Submission) -> Result<(), io::Error> {
+        self.submissions.push(submission);
+        self.store()
+    }
+
+    /// Check whether `answer` should be submitted at time `now` (seconds since the unix epoch).
+    pub fn check(&self, day: Day, part: Part, answer: &str, now: u64) -> Result<(), Refusal> {
+        // NOTE: the cooldown applies to the whole account, not only to this part.
+        if let Some(until) = self
+            .submissions
+            .iter()
+            .filter_map(Submission::wait_until)
+            .max()
+            && until > now
+        {
+            return Err(Refusal::Cooldown {
+                remaining: Duration::from_secs(until - now),
+            });
+        }
+
+        let previous = self
+            .submissions
+            .iter()
+            .filter(|s| s.day == day && s.part == part);
+
+        let mut lowest_too_high: Option<(i128, &str)> = None;
+        let mut highest_too_low: Option<(i128, &str)> = None;
+
+        for submission in previous {
+            match &submission.result {
+                SubmitResult::Correct => {
+                    return Err(Refusal::AlreadySolved {
+                        answer: submission.answer.clone(),
+                    });
+                }
+                SubmitResult::Incorrect { hint, .. } => {
+                    if submission.answer == answer {
+                        return Err(Refusal::AlreadyRejected);
+                    }
+
+                    let Ok(value) = submission.answer.parse::<i128>() else {
+                        continue;
+                    };
+
+                    match hint {
+                        Some(Hint::TooHigh) if lowest_too_high.is_none_or(|(x, _)| value < x) => {
+                            lowest_too_high = Some((value, &submission.answer));
+                        }
+                        Some(Hint::TooLow) if highest_too_low.is_none_or(|(x, _)| value > x) => {
+                            highest_too_low = Some((value, &submission.answer));
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            if let Some((bound, text)) = lowest_too_high
+                && value >= bound
+            {
+                return Err(Refusal::OutOfBounds {
+                    hint: Hint::TooHigh,
+                    bound: text.to_string(),
+                });
+            }
+
+            if let Some((bound, text)) = highest_too_low
+                && value <= bound
+            {
+                return Err(Refusal::OutOfBounds {
+                    hint: Hint::TooLow,
+                    bound: text.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn result_kind(result: &SubmitResult) -> &'static str {
+    match result {
+        SubmitResult::Correct => "correct",
+        SubmitResult::Incorrect { .. } => "incorrect",
+        SubmitResult::TooRecent { .. } => "too_recent",
+        SubmitResult::WrongLevel => "wrong_level",
+        SubmitResult::Unknown(_) => "unknown",
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.day.year())),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part".into(),
+            JsonValue::Number(f64::from(value.part.into_inner())),
+        );
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "result".into(),
+            JsonValue::String(result_kind(&value.result).into()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        let (hint, wait, message) = match &value.result {
+            SubmitResult::Incorrect { hint, wait } => (*hint, *wait, None),
+            SubmitResult::TooRecent { wait } => (None, *wait, None),
+            SubmitResult::Unknown(message) => (None, None, Some(message.clone())),
+            _ => (None, None, None),
+        };
+
+        map.insert(
+            "hint".into(),
+            hint.map_or(JsonValue::Null, |hint| {
+                JsonValue::String(
+                    match hint {
+                        Hint::TooHigh => "too_high",
+                        Hint::TooLow => "too_low",
+                    }
+                    .into(),
+                )
+            }),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "wait_seconds".into(),
+            wait.map_or(JsonValue::Null, |w| JsonValue::Number(w.as_secs() as f64)),
+        );
+        map.insert(
+            "message".into(),
+            message.map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected submission.{key} to be a string."))
+        };
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        // NOTE: submissions recorded before the year was stored belong to the default year.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<f64>())
+            .map_or(DEFAULT_YEAR, |year| *year as u16);
+
+        let day = Day::parse(&string("day")?, year).map_err(|e| e.to_string())?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (part, timestamp) = (number("part")? as u8, number("timestamp")? as u64);
+        let part = Part::new(part).ok_or("Expected submission.part to be 1 or 2.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let wait = json
+            .get("wait_seconds")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| Duration::from_secs(*x as u64));
+
+        let hint = match json.get("hint").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "too_high" => Some(Hint::TooHigh),
+            Some(x) if x == "too_low" => Some(Hint::TooLow),
+            _ => None,
+        };
+
+        let result = match string("result")?.as_str() {
+            "correct" => SubmitResult::Correct,
+            "incorrect" => SubmitResult::Incorrect { hint, wait },
+            "too_recent" => SubmitResult::TooRecent { wait },
+            "wrong_level" => SubmitResult::WrongLevel,
+            "unknown" => SubmitResult::Unknown(string("message").unwrap_or_default()),
+            x => return Err(format!("Unknown submission result `{x}`.")),
+        };
+
+        Ok(Submission {
+            day,
+            part,
+            answer: string("answer")?,
+            result,
+            timestamp,
+        })
+    }
+}
+
+impl From<&History> for JsonValue {
+    fn from(value: &History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "submissions".into(),
+            JsonValue::Array(value.submissions.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let submissions = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("submissions")
+            .ok_or("expected JSON document to have key `submissions`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.submissions` to be an array.")?;
+
+        Ok(History {
+            submissions: submissions
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{History, Refusal, Submission};
+    use crate::{
+        day,
+        template::{
+            Part,
+            aoc_client::{Hint, SubmitResult},
+        },
+    };
+
+    fn submission(answer: &str, result: SubmitResult, timestamp: u64) -> Submission {
+        Submission {
+            day: day!(1),
+            part: Part::One,
+            answer: answer.into(),
+            result,
+            timestamp,
+        }
+    }
+
+    fn incorrect(hint: Option<Hint>) -> SubmitResult {
+        SubmitResult::Incorrect {
+            hint,
+            wait: Some(Duration::from_secs(60)),
+        }
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let history = History {
+            submissions: vec![submission("abc", incorrect(None), 0)],
+        };
+        assert_eq!(
+            history.check(day!(1), Part::One, "abc", 100),
+            Err(Refusal::AlreadyRejected)
+        );
+        assert_eq!(history.check(day!(1), Part::One, "abd", 100), Ok(()));
+        assert_eq!(history.check(day!(1), Part::Two, "abc", 100), Ok(()));
+        assert_eq!(history.check(day!(2), Part::One, "abc", 100), Ok(()));
+        assert_eq!(
+            history.check(day!(1).with_year(2015), Part::One, "abc", 100),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let history = History {
+            submissions: vec![
+                submission("100", incorrect(Some(Hint::TooHigh)), 0),
+                submission("150", incorrect(Some(Hint::TooHigh)), 0),
+                submission("20", incorrect(Some(Hint::TooLow)), 0),
+            ],
+        };
+        assert_eq!(
+            history.check(day!(1), Part::One, "100", 100),
+            Err(Refusal::AlreadyRejected)
+        );
+        assert_eq!(
+            history.check(day!(1), Part::One, "120", 100),
+            Err(Refusal::OutOfBounds {
+                hint: Hint::TooHigh,
+                bound: "100".into()
+            })
+        );
+        assert_eq!(
+            history.check(day!(1), Part::One, "-5", 100),
+            Err(Refusal::OutOfBounds {
+                hint: Hint::TooLow,
+                bound: "20".into()
+            })
+        );
+        assert_eq!(history.check(day!(1), Part::One, "50", 100), Ok(()));
+        assert_eq!(
+            history.check(day!(1), Part::One, "not a number", 100),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn enforces_cooldown() {
+        let history = History {
+            submissions: vec![
+                submission("1", incorrect(None), 1000),
+                Submission {
+                    day: day!(2),
+                    ..submission(
+                        "2",
+                        SubmitResult::TooRecent {
+                            wait: Some(Duration::from_secs(30)),
+                        },
+                        1010,
+                    )
+                },
+            ],
+        };
+        assert_eq!(
+            history.check(day!(1), Part::One, "3", 1020),
+            Err(Refusal::Cooldown {
+                remaining: Duration::from_secs(40)
+            })
+        );
+        assert_eq!(history.check(day!(1), Part::One, "3", 1060), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let history = History {
+            submissions: vec![submission("42", SubmitResult::Correct, 0)],
+        };
+        assert_eq!(
+            history.check(day!(1), Part::One, "43", 100),
+            Err(Refusal::AlreadySolved {
+                answer: "42".into()
+            })
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let history = History {
+            submissions: vec![
+                submission("100", incorrect(Some(Hint::TooLow)), 5),
+                submission("42", SubmitResult::Correct, 10),
+                submission("7", SubmitResult::Unknown("huh".into()), 20),
+                submission("8", SubmitResult::TooRecent { wait: None }, 30),
+                Submission {
+                    day: day!(2).with_year(2015),
+                    ..submission("9", SubmitResult::Correct, 40)
+                },
+            ],
+        };
+        let json = JsonValue::from(&history).stringify().unwrap();
+        assert_eq!(History::try_from(json).unwrap(), history);
+    }
+}
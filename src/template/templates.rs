@@ -0,0 +1,195 @@
+/// Module templates used by `cargo scaffold`.
+///
+/// Built-in templates are compiled into the binary, user-defined ones are read from
+/// `templates/<name>.txt` and take precedence over a built-in template of the same name.
+use std::error::Error;
+use std::fmt::Display;
+use std::{fs, io};
+
+use crate::template::{Day, Part, aoc_cli, data_dir};
+
+/// Directory of user-defined templates, relative to the project root.
+pub const TEMPLATE_DIR: &str = "templates";
+
+/// Template used if `--template` is not set.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+macro_rules! builtin {
+    ($name:literal, $path:literal) => {
+        (
+            $name,
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path)),
+        )
+    };
+}
+
+const BUILTIN: [(&str, &str); 5] = [
+    builtin!("default", "src/template.txt"),
+    builtin!("grid", "src/templates/grid.txt"),
+    builtin!("numbers", "src/templates/numbers.txt"),
+    builtin!("graph", "src/templates/graph.txt"),
+    builtin!("shared-parse", "src/templates/shared-parse.txt"),
+];
+
+/// Names of all available templates, sorted.
+pub fn names() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN.iter().map(|(name, _)| name.to_string()).collect();
+
+    if let Ok(entries) = fs::read_dir(TEMPLATE_DIR) {
+        names.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let is_template = path.extension().is_some_and(|ext| ext == "txt");
+            is_template.then(|| path.file_stem()?.to_str().map(String::from))?
+        }));
+    }
+
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Reads the template called `name`, preferring `templates/<name>.txt` over a built-in template.
+pub fn load(name: &str) -> Result<String, TemplateError> {
+    match fs::read_to_string(format!("{TEMPLATE_DIR}/{name}.txt")) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => BUILTIN
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, template)| template.to_string())
+            .ok_or_else(|| TemplateError::Unknown(name.into())),
+        Err(e) => Err(TemplateError::Read(e)),
+    }
+}
+
+/// Values of the placeholders that can be used in a template.
+///
+/// The title is taken from the downloaded description in `data/{year}/puzzles/{day}.md`,
+/// falling back to `Day <n>` if it hasn't been downloaded yet.
+pub fn placeholders(day: Day) -> Vec<(&'static str, String)> {
+    let title = fs::read_to_string(aoc_cli::get_puzzle_path(day))
+        .ok()
+        .and_then(|puzzle| puzzle_title(&puzzle));
+
+    placeholders_with_title(day, title)
+}
+
+fn placeholders_with_title(day: Day, title: Option<String>) -> Vec<(&'static str, String)> {
+    let examples = format!("{}/examples", data_dir(day.year()));
+    let example_part = |part: Part| format!("{examples}/{day}-{part}.txt");
+
+    vec![
+        ("%DAY_NUMBER%", day.into_inner().to_string()),
+        ("%DAY%", day.to_string()),
+        ("%YEAR%", day.year().to_string()),
+        (
+            "%TITLE%",
+            title.unwrap_or_else(|| format!("Day {}", day.into_inner())),
+        ),
+        ("%EXAMPLE_FILE%", format!("{examples}/{day}.txt")),
+        ("%EXAMPLE_FILE_PART_ONE%", example_part(Part::One)),
+        ("%EXAMPLE_FILE_PART_TWO%", example_part(Part::Two)),
+    ]
+}
+
+/// Replaces the placeholders of `template`.
+pub fn render(template: &str, placeholders: &[(&str, String)]) -> String {
+    placeholders
+        .iter()
+        .fold(template.to_string(), |rendered, (placeholder, value)| {
+            rendered.replace(placeholder, value)
+        })
+}
+
+/// Finds the title of a puzzle description, e.g. `Day 1: Trebuchet?!` in `## --- Day 1: Trebuchet?! ---`.
+fn puzzle_title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let title = line
+            .trim_start_matches('#')
+            .trim()
+            .strip_prefix("--- ")?
+            .strip_suffix(" ---")?;
+        title.starts_with("Day ").then(|| title.into())
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when loading a template.
+#[derive(Debug)]
+pub enum TemplateError {
+    Unknown(String),
+    Read(io::Error),
+}
+
+impl Error for TemplateError {}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::Unknown(name) => write!(
+                f,
+                "unknown template `{name}`, available templates: {}",
+                names().join(", ")
+            ),
+            TemplateError::Read(e) => write!(f, "failed to read template: {e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BUILTIN, load, placeholders_with_title, puzzle_title, render};
+    use crate::template::Day;
+
+    fn day(day: u8) -> Day {
+        Day::of_year(2024, day).unwrap()
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = placeholders_with_title(day(5), Some("Day 5: Print Queue".into()));
+        let template = "%TITLE% (%YEAR%/%DAY%): solution!(%DAY_NUMBER%), %EXAMPLE_FILE% and %EXAMPLE_FILE_PART_TWO%";
+
+        assert_eq!(
+            render(template, &placeholders),
+            "Day 5: Print Queue (2024/05): solution!(5), data/2024/examples/05.txt and data/2024/examples/05-2.txt"
+        );
+        assert_eq!(
+            render("%TITLE%", &placeholders_with_title(day(5), None)),
+            "Day 5"
+        );
+    }
+
+    #[test]
+    fn renders_builtin_templates() {
+        let placeholders = placeholders_with_title(day(12), None);
+
+        for (name, template) in BUILTIN {
+            let rendered = render(template, &placeholders);
+            assert!(!rendered.contains('%'), "{name} has unknown placeholders");
+            assert!(
+                rendered.contains("solution!(12"),
+                "{name} declares no solution"
+            );
+        }
+    }
+
+    #[test]
+    fn finds_puzzle_titles() {
+        assert_eq!(
+            puzzle_title("## --- Day 1: Trebuchet?! ---\n\nSomething *is* wrong."),
+            Some("Day 1: Trebuchet?!".into())
+        );
+        assert_eq!(puzzle_title("Intro\n\n## --- Part Two ---\n"), None);
+    }
+
+    #[test]
+    fn rejects_unknown_templates() {
+        assert!(load("grid").unwrap().contains("type Grid"));
+        assert!(load("does-not-exist").unwrap_err().to_string().starts_with(
+            "unknown template `does-not-exist`, available templates: default, graph, grid"
+        ));
+    }
+}
@@ -1,19 +1,115 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{
+    Day, Part,
+    answers::Verdict,
+    data_dir,
+    memory::AllocStats,
+    protocol::{PartRecord, Phase, Status},
+    runner::{BenchConfig, BenchStats},
+};
 
 /// Represents benchmark times for a single day.
+/// The mean time and the number of samples of a part are those of its stats.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse_stats: Option<BenchStats>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    pub parse_status: Option<Status>,
+    pub part_1_status: Option<Status>,
+    pub part_2_status: Option<Status>,
+    pub parse_memory: Option<AllocStats>,
+    pub part_1_memory: Option<AllocStats>,
+    pub part_2_memory: Option<AllocStats>,
+    /// Comparison of the answers against the known answers when the parts were timed.
+    pub part_1_verdict: Option<Verdict>,
+    pub part_2_verdict: Option<Verdict>,
+    /// Settings the parts were benched with.
+    pub bench: Option<BenchConfig>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// A timing of `day` without any parts.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_status: None,
+            part_1_status: None,
+            part_2_status: None,
+            parse_memory: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            part_1_verdict: None,
+            part_2_verdict: None,
+            bench: None,
+            total_nanos: 0_f64,
+        }
+    }
+
+    /// Collect the timings of benched parts. Records without stats were not benched, only their status is kept.
+    pub fn from_records(records: &[PartRecord], day: Day) -> Self {
+        let mut timing = Timing::new(day);
+
+        for record in records {
+            let (stats, status, memory) = match record.part {
+                Phase::Parse => (
+                    &mut timing.parse_stats,
+                    &mut timing.parse_status,
+                    &mut timing.parse_memory,
+                ),
+                Phase::Part(Part::One) => {
+                    timing.part_1_verdict = Some(record.verdict.clone());
+                    (
+                        &mut timing.part_1_stats,
+                        &mut timing.part_1_status,
+                        &mut timing.part_1_memory,
+                    )
+                }
+                Phase::Part(Part::Two) => {
+                    timing.part_2_verdict = Some(record.verdict.clone());
+                    (
+                        &mut timing.part_2_stats,
+                        &mut timing.part_2_status,
+                        &mut timing.part_2_memory,
+                    )
+                }
+            };
+
+            *status = Some(record.status);
+            *memory = record.memory;
+
+            if record.stats.is_none() {
+                continue;
+            }
+
+            *stats = record.stats;
+
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timing.total_nanos += record.nanos as f64;
+            }
+        }
+
+        timing
+    }
+}
+
+/// Version of the schema of `timings.json` written by [`Timings::store_file`].
+pub const TIMINGS_VERSION: u32 = 3;
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,19 +118,29 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Path of the timings of a year, e.g. `data/2025/timings.json`.
+    fn path(year: u16) -> String {
+        format!("{}/timings.json", data_dir(year))
+    }
+
+    /// Dehydrate timings of `year` to a JSON file.
+    pub fn store_file(&self, year: u16) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(Self::path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Rehydrate timings of `year` from a JSON file. If not present, returns empty timings.
+    ///
+    /// Fails if the file exists but cannot be read or parsed, so that storing new timings does not erase it.
+    pub fn read_from_file(year: u16) -> Result<Self, String> {
+        let path = Self::path(year);
+        match fs::read_to_string(&path) {
+            Ok(s) => Timings::parse(&s, year)
+                .map_err(|e| format!("\"{path}\" is corrupt, please fix or remove it: {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("failed to read \"{path}\": {e}")),
+        }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,10 +157,33 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// Merge the allocations of `new` into `self`, keeping the times of `self`.
+    /// Used for timings recorded with the `count-allocs` feature, whose times include the counting overhead.
+    pub fn merge_memory(&self, new: &Self) -> Self {
+        let data: Vec<Timing> = new
+            .data
+            .iter()
+            .map(|timing| {
+                let mut merged = self
+                    .data
+                    .iter()
+                    .find(|t| t.day == timing.day)
+                    .cloned()
+                    .unwrap_or_else(|| Timing::new(timing.day));
+                merged.parse_memory = timing.parse_memory;
+                merged.part_1_memory = timing.part_1_memory;
+                merged.part_2_memory = timing.part_2_memory;
+                merged
+            })
+            .collect();
+
+        self.merge(&Timings { data })
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
@@ -63,7 +192,7 @@ impl Timings {
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.day == day && t.part_1_stats.is_some() && t.part_2_stats.is_some())
     }
 }
 
@@ -73,6 +202,10 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(TIMINGS_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -82,15 +215,35 @@ impl From<Timings> for JsonValue {
     }
 }
 
-impl TryFrom<String> for Timings {
-    type Error = String;
-
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+impl Timings {
+    /// Parses the timings stored in `data/{year}/timings.json`.
+    ///
+    /// NOTE: files written before the year was stored only contain day numbers,
+    /// their location determines the year.
+    pub fn parse(value: &str, year: u16) -> Result<Self, String> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let document = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // NOTE: documents written before the version was added are of version 1.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let version = match document.get("version") {
+            None => 1,
+            Some(version) => version
+                .get::<f64>()
+                .map(|version| *version as u32)
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+
+        if version > TIMINGS_VERSION {
+            return Err(format!(
+                "timings of version {version} are newer than this template supports ({TIMINGS_VERSION})."
+            ));
+        }
+
+        let json_data = document
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -99,12 +252,79 @@ impl TryFrom<String> for Timings {
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(|timing| match version {
+                    TIMINGS_VERSION => Timing::from_json(timing, year),
+                    _ => Timing::from_json(&migrate(timing, version)?, year),
+                })
                 .collect::<Result<_, _>>()?,
         })
     }
 }
 
+/// Migrates a timing of an older version, which stored the mean of each part next to its stats:
+/// version 1 as a display string like `"74.1ms"`, version 2 as `{part}_nanos` with `{part}_samples`.
+/// Parts that were stored without stats get stats of their mean alone.
+fn migrate(value: &JsonValue, version: u32) -> Result<JsonValue, String> {
+    let mut json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing to be a JSON object.")?
+        .clone();
+
+    for key in ["parse", "part_1", "part_2"] {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let nanos = if version == 1 {
+            // NOTE: parse timings were added later, files written before that have no such key.
+            match json.remove(key) {
+                None if key == "parse" => None,
+                Some(JsonValue::Null) => None,
+                Some(JsonValue::String(formatted)) => Some(parse_nanos(&formatted).ok_or(
+                    format!("Expected timing.{key} to be a duration like `74.1ms`."),
+                )?),
+                _ => return Err(format!("Expected timing.{key} to be null or string.")),
+            }
+        } else {
+            json.remove(&format!("{key}_samples"));
+            match json.remove(&format!("{key}_nanos")) {
+                None | Some(JsonValue::Null) => None,
+                Some(JsonValue::Number(nanos)) => Some(nanos as u64),
+                _ => {
+                    return Err(format!(
+                        "Expected timing.{key}_nanos to be null or a number."
+                    ));
+                }
+            }
+        };
+
+        let stats_key = format!("{key}_stats");
+        let has_stats = json.get(&stats_key).is_some_and(|stats| !stats.is_null());
+
+        if let (Some(nanos), false) = (nanos, has_stats) {
+            let stats = BenchStats::from_samples(&[Duration::from_nanos(nanos)]);
+            json.insert(stats_key, JsonValue::from(&stats));
+        }
+    }
+
+    Ok(JsonValue::Object(json))
+}
+
+/// Parses a duration formatted with `{:.1?}` to nanoseconds, e.g. `74.1ms`.
+fn parse_nanos(formatted: &str) -> Option<u64> {
+    let split = formatted.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = formatted.split_at(split);
+
+    let nanos_per_unit = match unit {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    let nanos = (value.parse::<f64>().ok()? * nanos_per_unit).round();
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (nanos.is_finite() && nanos >= 0.0).then_some(nanos as u64)
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<&Timing> for JsonValue {
@@ -112,64 +332,165 @@ impl From<&Timing> for JsonValue {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.day.year())),
+        );
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        for (key, stats) in [
+            ("parse_stats", value.parse_stats),
+            ("part_1_stats", value.part_1_stats),
+            ("part_2_stats", value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                stats.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
 
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        for (key, status) in [
+            ("parse_status", value.parse_status),
+            ("part_1_status", value.part_1_status),
+            ("part_2_status", value.part_2_status),
+        ] {
+            map.insert(
+                key.into(),
+                status.map_or(JsonValue::Null, |s| JsonValue::String(s.to_string())),
+            );
+        }
+
+        for (key, verdict) in [
+            ("part_1", &value.part_1_verdict),
+            ("part_2", &value.part_2_verdict),
+        ] {
+            map.insert(
+                format!("{key}_verdict"),
+                verdict
+                    .as_ref()
+                    .map_or(JsonValue::Null, |v| JsonValue::String(v.to_string())),
+            );
+
+            if let Some(Verdict::Incorrect { expected }) = verdict {
+                map.insert(
+                    format!("{key}_expected"),
+                    JsonValue::String(expected.clone()),
+                );
+            }
+        }
 
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "bench".into(),
+            value
+                .bench
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
+        for (key, memory) in [
+            ("parse_memory", value.parse_memory),
+            ("part_1_memory", value.part_1_memory),
+            ("part_2_memory", value.part_2_memory),
+        ] {
+            map.insert(
+                key.into(),
+                memory.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
 
-impl TryFrom<&JsonValue> for Timing {
-    type Error = String;
-
-    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+impl Timing {
+    /// Parses a stored timing, `year` is used for timings that were stored without one.
+    fn from_json(value: &JsonValue, year: u16) -> Result<Self, String> {
         let json = value
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<f64>())
+            .map_or(year, |year| *year as u16);
+
         let day = json
             .get("day")
             .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
+            .and_then(|day| Day::parse(day, year).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: stats, statuses and memory were added later, files written before that have no such keys.
+        let parse_stats = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => BenchStats::try_from(v).map(Some),
+        };
+
+        let parse_status = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .ok_or(format!("Expected timing.{key} to be null or string."))?
+                .parse()
+                .map(Some),
+        };
+
+        let parse_memory = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => AllocStats::try_from(v).map(Some),
+        };
+
+        // NOTE: verdicts were added later, files written before that have no such keys.
+        let parse_verdict = |key: &str| match json.get(&format!("{key}_verdict")) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => match v.get::<String>().map(String::as_str) {
+                Some("correct") => Ok(Some(Verdict::Correct)),
+                Some("incorrect") => json
+                    .get(&format!("{key}_expected"))
+                    .and_then(|v| v.get::<String>())
+                    .map(|expected| {
+                        Some(Verdict::Incorrect {
+                            expected: expected.clone(),
+                        })
+                    })
+                    .ok_or(format!("Expected timing.{key}_expected to be a string.")),
+                Some("unknown") => Ok(Some(Verdict::Unknown)),
+                _ => Err(format!(
+                    "Expected timing.{key}_verdict to be one of correct, incorrect or unknown."
+                )),
+            },
+        };
+
+        let bench = match json.get("bench") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchConfig::try_from(v)?),
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse_stats: parse_stats("parse_stats")?,
+            part_1_stats: parse_stats("part_1_stats")?,
+            part_2_stats: parse_stats("part_2_stats")?,
+            parse_status: parse_status("parse_status")?,
+            part_1_status: parse_status("part_1_status")?,
+            part_2_status: parse_status("part_2_status")?,
+            parse_memory: parse_memory("parse_memory")?,
+            part_1_memory: parse_memory("part_1_memory")?,
+            part_2_memory: parse_memory("part_2_memory")?,
+            part_1_verdict: parse_verdict("part_1")?,
+            part_2_verdict: parse_verdict("part_2")?,
+            bench,
             total_nanos,
         })
     }
@@ -177,56 +498,385 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, duration) in [
+            ("mean_nanos", value.mean),
+            ("median_nanos", value.median),
+            ("min_nanos", value.min),
+            ("max_nanos", value.max),
+            ("std_dev_nanos", value.std_dev),
+            ("p95_nanos", value.p95),
+            ("p99_nanos", value.p99),
+        ] {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert(key.into(), JsonValue::Number(duration.as_nanos() as f64));
+        }
+
+        for (key, count) in [("samples", value.samples), ("outliers", value.outliers)] {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert(key.into(), JsonValue::Number(count as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = |key: &str| number(key).map(|x| x as u128);
+
+        Ok(BenchStats {
+            mean: duration("mean_nanos")?,
+            median: duration("median_nanos")?,
+            min: duration("min_nanos")?,
+            max: duration("max_nanos")?,
+            std_dev: duration("std_dev_nanos")?,
+            p95: duration("p95_nanos")?,
+            p99: duration("p99_nanos")?,
+            samples: count("samples")?,
+            outliers: count("outliers")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchConfig> for JsonValue {
+    fn from(value: &BenchConfig) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "budget_nanos".into(),
+                JsonValue::Number(value.budget.as_nanos() as f64),
+            );
+            map.insert(
+                "min_iterations".into(),
+                JsonValue::Number(value.min_iterations as f64),
+            );
+            map.insert(
+                "max_iterations".into(),
+                JsonValue::Number(value.max_iterations as f64),
+            );
+            map.insert(
+                "iterations".into(),
+                value
+                    .iterations
+                    .map_or(JsonValue::Null, |n| JsonValue::Number(n as f64)),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchConfig {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected bench config to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected bench config.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = |key: &str| number(key).map(|x| x as u128);
+
+        let iterations = match json.get("iterations") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(_) => Some(count("iterations")?),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchConfig {
+            budget: Duration::from_nanos(number("budget_nanos")? as u64),
+            min_iterations: count("min_iterations")?,
+            max_iterations: count("max_iterations")?,
+            iterations,
+        })
+    }
+}
+
+impl From<&AllocStats> for JsonValue {
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, count) in [
+            ("allocations", value.allocations),
+            ("bytes", value.bytes),
+            ("peak_bytes", value.peak_bytes),
+        ] {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert(key.into(), JsonValue::Number(count as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let count = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("Expected memory stats.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            allocations: count("allocations")?,
+            bytes: count("bytes")?,
+            peak_bytes: count("peak_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use std::time::Duration;
+
+    use crate::{day, template::Day, template::runner::BenchStats};
 
     use super::{Timing, Timings};
 
+    /// A timing of `day` whose parts were benched once for the given number of nanoseconds.
+    pub(super) fn timing(day: Day, part_1: Option<u64>, part_2: Option<u64>) -> Timing {
+        let stats = |nanos: u64| BenchStats::from_samples(&[Duration::from_nanos(nanos)]);
+
+        #[allow(clippy::cast_precision_loss)]
+        Timing {
+            part_1_stats: part_1.map(stats),
+            part_2_stats: part_2.map(stats),
+            total_nanos: (part_1.unwrap_or(0) + part_2.unwrap_or(0)) as f64,
+            ..Timing::new(day)
+        }
+    }
+
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
-                },
+                timing(day!(1), Some(10_000_000), Some(20_000_000)),
+                timing(day!(2), Some(30_000_000), Some(40_000_000)),
+                timing(day!(4), Some(40_000_000), None),
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{DEFAULT_YEAR, answers::Verdict, protocol::Status, timings::Timings},
+        };
+        use std::time::Duration;
+        use tinyjson::JsonValue;
 
         #[test]
         fn handles_json_timings() {
             let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
-            let timings = Timings::try_from(json).unwrap();
+            let timings = Timings::parse(&json, DEFAULT_YEAR).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
-            assert_eq!(timing.part_2, None);
+            assert_eq!(
+                timing.part_1_stats.map(|s| s.mean),
+                Some(Duration::from_millis(1))
+            );
+            assert_eq!(timing.part_1_stats.map(|s| s.samples), Some(1));
+            assert_eq!(timing.part_2_stats, None);
+            assert_eq!(timing.parse_stats, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_statuses() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_status": "solved", "part_2_status": "panicked" }] }"#.to_string();
+            let timings = Timings::parse(&json, DEFAULT_YEAR).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse_status, None);
+            assert_eq!(timing.part_1_status, Some(Status::Solved));
+            assert_eq!(timing.part_2_status, Some(Status::Panicked));
+        }
+
+        #[test]
+        fn handles_json_parse_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "3ms", "parse_stats": null, "part_1": "1ms", "part_2": "2ms", "total_nanos": 6000000 }] }"#.to_string();
+            let timings = Timings::parse(&json, DEFAULT_YEAR).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.parse_stats.map(|s| s.mean),
+                Some(Duration::from_millis(3))
+            );
+        }
+
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "mean_nanos": 1000000, "median_nanos": 990000, "min_nanos": 900000, "max_nanos": 1200000, "std_dev_nanos": 5000, "p95_nanos": 1100000, "p99_nanos": 1150000, "samples": 1000, "outliers": 3 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::parse(&json, DEFAULT_YEAR).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.unwrap();
+            assert_eq!(stats.mean, Duration::from_millis(1));
+            assert_eq!(stats.median, Duration::from_nanos(990_000));
+            assert_eq!(stats.p99, Duration::from_nanos(1_150_000));
+            assert_eq!(stats.samples, 1000);
+            assert_eq!(stats.outliers, 3);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_json_verdicts() {
+            let json = r#"{ "version": 3, "data": [{ "day": "01", "total_nanos": 0, "part_1_verdict": "correct", "part_2_verdict": "incorrect", "part_2_expected": "42" }, { "day": "02", "total_nanos": 0 }] }"#;
+            let timings = Timings::parse(json, DEFAULT_YEAR).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_verdict, Some(Verdict::Correct));
+            assert_eq!(
+                timing.part_2_verdict,
+                Some(Verdict::Incorrect {
+                    expected: "42".into()
+                })
+            );
+            assert_eq!(timings.data[1].part_1_verdict, None);
+
+            let stored = JsonValue::from(timings).format().unwrap();
+            let timings = Timings::parse(&stored, DEFAULT_YEAR).unwrap();
+            assert_eq!(
+                timings.data[0].part_2_verdict,
+                Some(Verdict::Incorrect {
+                    expected: "42".into()
+                })
+            );
+
+            let json = r#"{ "version": 3, "data": [{ "day": "01", "total_nanos": 0, "part_1_verdict": "incorrect" }] }"#;
+            assert_eq!(
+                Timings::parse(json, DEFAULT_YEAR).unwrap_err(),
+                "Expected timing.part_1_expected to be a string."
+            );
+        }
+
+        #[test]
+        fn handles_json_memory() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_memory": { "allocations": 12, "bytes": 4096, "peak_bytes": 1024 } }] }"#.to_string();
+            let timings = Timings::parse(&json, DEFAULT_YEAR).unwrap();
+            let timing = timings.data.first().unwrap();
+            let memory = timing.part_1_memory.unwrap();
+            assert_eq!(memory.allocations, 12);
+            assert_eq!(memory.bytes, 4096);
+            assert_eq!(memory.peak_bytes, 1024);
+            assert_eq!(timing.parse_memory, None);
+        }
+
+        #[test]
+        fn handles_json_bench_config() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "bench": { "budget_nanos": 5000000000, "min_iterations": 2, "max_iterations": 100, "iterations": null } }, { "day": "02", "part_1": null, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::parse(&json, DEFAULT_YEAR).unwrap();
+            let bench = timings.data[0].bench.unwrap();
+            assert_eq!(bench.budget, Duration::from_secs(5));
+            assert_eq!(bench.min_iterations, 2);
+            assert_eq!(bench.max_iterations, 100);
+            assert_eq!(bench.iterations, None);
+            assert_eq!(timings.data[1].bench, None);
+        }
+
+        #[test]
+        fn migrates_formatted_durations() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2.5µs", "part_1": "74.1ms", "part_2": "1.2s", "total_nanos": 1274102500 }] }"#.to_string();
+            let timings = Timings::parse(&json, DEFAULT_YEAR).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.parse_stats.map(|s| s.mean),
+                Some(Duration::from_nanos(2_500))
+            );
+            assert_eq!(
+                timing.part_1_stats.map(|s| s.mean),
+                Some(Duration::from_nanos(74_100_000))
+            );
+            assert_eq!(
+                timing.part_2_stats.map(|s| s.mean),
+                Some(Duration::from_nanos(1_200_000_000))
+            );
+
+            let json = r#"{ "data": [{ "day": "01", "part_1": "fast", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            assert_eq!(
+                Timings::parse(&json, DEFAULT_YEAR).unwrap_err(),
+                "Expected timing.part_1 to be a duration like `74.1ms`."
+            );
+        }
+
+        #[test]
+        fn uses_year_of_file_for_timings_without_year() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000 }, { "day": "13", "part_1": null, "part_2": "2ms", "total_nanos": 2000000 }] }"#;
+            let timings = Timings::parse(json, 2024).unwrap();
+            assert_eq!(timings.data.len(), 2);
+            assert_eq!(timings.data[0].day, day!(1).with_year(2024));
+            assert_eq!(timings.data[1].day.year(), 2024);
+            assert_eq!(timings.data[1].day.into_inner(), 13);
+
+            assert!(Timings::parse(json, 2025).is_err());
+        }
+
+        #[test]
+        fn handles_versioned_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "year": 2024, "parse_nanos": null, "parse_samples": null, "part_1_nanos": 74130000, "part_1_samples": 12, "part_2_nanos": null, "part_2_samples": null, "total_nanos": 74130000 }] }"#.to_string();
+            let timings = Timings::parse(&json, DEFAULT_YEAR).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.part_1_stats.map(|s| s.mean),
+                Some(Duration::from_nanos(74_130_000))
+            );
+            assert_eq!(timing.part_1_stats.map(|s| s.samples), Some(1));
+            assert_eq!(timing.part_2_stats, None);
+
+            let json = r#"{ "version": 3, "data": [{ "day": "01", "year": 2024, "part_1_stats": { "mean_nanos": 1000000, "median_nanos": 990000, "min_nanos": 900000, "max_nanos": 1200000, "std_dev_nanos": 5000, "p95_nanos": 1100000, "p99_nanos": 1150000, "samples": 12, "outliers": 0 }, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::parse(&json, DEFAULT_YEAR).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_stats.map(|s| s.samples), Some(12));
+            assert_eq!(timing.parse_stats, None);
+
+            let json = r#"{ "version": 4, "data": [] }"#.to_string();
+            assert_eq!(
+                Timings::parse(&json, DEFAULT_YEAR).unwrap_err(),
+                "timings of version 4 are newer than this template supports (3)."
+            );
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
-            let timings = Timings::try_from(json).unwrap();
+            let timings = Timings::parse(&json, DEFAULT_YEAR).unwrap();
             assert_eq!(timings.data.len(), 0);
         }
 
@@ -234,14 +884,14 @@ mod tests {
         #[should_panic]
         fn panics_for_invalid_json() {
             let json = r#"{}"#.to_string();
-            Timings::try_from(json).unwrap();
+            Timings::parse(&json, DEFAULT_YEAR).unwrap();
         }
 
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
             let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
-            Timings::try_from(json).unwrap();
+            Timings::parse(&json, DEFAULT_YEAR).unwrap();
         }
     }
 
@@ -269,72 +919,181 @@ mod tests {
     }
 
     mod is_day_complete {
-        use crate::{
-            day,
-            template::timings::{Timing, Timings},
-        };
+        use crate::{day, template::timings::Timings};
+
+        use super::timing;
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
-                }],
+                data: vec![timing(day!(1), Some(1_000_000), Some(2_000_000))],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
-                }],
+                data: vec![timing(day!(1), Some(1_000_000), None)],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![timing(day!(1), None, None)],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod from_records {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                Part,
+                answers::Verdict,
+                protocol::{PartRecord, Phase, Status},
+                runner::{BenchStats, Failure},
+                timings::Timing,
+            },
+        };
+
+        fn record(part: Phase, answer: Option<&str>, nanos: u64) -> PartRecord {
+            let stats = BenchStats::from_samples(&[Duration::from_nanos(nanos)]);
+            PartRecord::new(
+                part,
+                answer.map(Into::into),
+                Duration::from_nanos(nanos),
+                Some(stats),
+            )
+        }
+
+        #[test]
+        fn collects_timings() {
+            let res = Timing::from_records(
+                &[
+                    record(Phase::Parse, None, 2_000_000),
+                    record(Phase::Part(Part::One), Some("0"), 74),
+                    record(Phase::Part(Part::Two), Some("10"), 74_130_000),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.total_nanos, 76_130_074_f64);
+            assert_eq!(
+                res.parse_stats.map(|s| s.mean),
+                Some(Duration::from_nanos(2_000_000))
+            );
+            assert_eq!(
+                res.part_1_stats.map(|s| s.mean),
+                Some(Duration::from_nanos(74))
+            );
+            assert_eq!(res.part_1_stats.map(|s| s.samples), Some(1));
+            assert_eq!(
+                res.part_2_stats.map(|s| s.mean),
+                Some(Duration::from_nanos(74_130_000))
+            );
+            assert_eq!(res.part_1_stats.unwrap().samples, 1);
+        }
+
+        #[test]
+        fn keeps_verdicts() {
+            let mut part_1 = record(Phase::Part(Part::One), Some("0"), 74);
+            part_1.verdict = Verdict::Correct;
+            let mut part_2 = record(Phase::Part(Part::Two), Some("10"), 74);
+            part_2.verdict = Verdict::Incorrect {
+                expected: "11".into(),
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            let res = Timing::from_records(&[part_1, part_2], day!(1));
+            assert_eq!(res.part_1_verdict, Some(Verdict::Correct));
+            assert_eq!(
+                res.part_2_verdict,
+                Some(Verdict::Incorrect {
+                    expected: "11".into()
+                })
+            );
+        }
+
+        #[test]
+        fn collects_timings_with_patterns_in_answers() {
+            let res = Timing::from_records(
+                &[
+                    record(
+                        Phase::Part(Part::One),
+                        Some("@ @ @ ( ) ms (2s @ 5 samples)"),
+                        2_000_000_000,
+                    ),
+                    record(Phase::Part(Part::Two), Some("10s"), 100_000_000),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.total_nanos, 2_100_000_000_f64);
+            assert_eq!(
+                res.part_1_stats.map(|s| s.mean),
+                Some(Duration::from_nanos(2_000_000_000))
+            );
+            assert_eq!(
+                res.part_2_stats.map(|s| s.mean),
+                Some(Duration::from_nanos(100_000_000))
+            );
+        }
+
+        #[test]
+        fn keeps_failure_status() {
+            let res = Timing::from_records(
+                &[
+                    record(Phase::Part(Part::One), Some("0"), 74),
+                    PartRecord::failed(
+                        Phase::Part(Part::Two),
+                        &Failure::TimedOut(Duration::from_secs(1)),
+                    ),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.total_nanos, 74_f64);
+            assert_eq!(res.part_1_status, Some(Status::Solved));
+            assert_eq!(res.part_2_stats, None);
+            assert_eq!(res.part_2_status, Some(Status::TimedOut));
+        }
+
+        #[test]
+        fn skips_records_that_were_not_benched() {
+            let res = Timing::from_records(
+                &[
+                    PartRecord::new(Phase::Part(Part::One), None, Duration::from_millis(1), None),
+                    PartRecord::new(Phase::Part(Part::Two), None, Duration::from_millis(1), None),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1_stats.is_none(), true);
+            assert_eq!(res.part_2_stats.is_none(), true);
         }
     }
 
     mod merge {
+        use std::time::Duration;
+
         use crate::{
             day,
+            template::memory::AllocStats,
             template::timings::{Timing, Timings},
         };
 
-        use super::get_mock_timings;
+        use super::{get_mock_timings, timing};
 
         #[test]
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +1108,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
             };
             let merged = timings.merge(&other);
 
@@ -365,6 +1119,34 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_times_when_merging_memory() {
+            let timings = get_mock_timings();
+            let memory = AllocStats {
+                allocations: 3,
+                bytes: 276,
+                peak_bytes: 232,
+            };
+
+            let mut counted = timing(day!(2), Some(99_000_000), None);
+            counted.part_1_memory = Some(memory);
+            let mut new_day = Timing::new(day!(3));
+            new_day.part_2_memory = Some(memory);
+
+            let merged = timings.merge_memory(&Timings {
+                data: vec![counted, new_day],
+            });
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(
+                merged.data[1].part_1_stats.map(|s| s.mean),
+                Some(Duration::from_millis(30))
+            );
+            assert_eq!(merged.data[1].part_1_memory, Some(memory));
+            assert_eq!(merged.data[2].day, day!(3));
+            assert_eq!(merged.data[2].part_1_stats, None);
+            assert_eq!(merged.data[2].part_2_memory, Some(memory));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,218 @@
+/// Waits for the next puzzle to unlock, used by `cargo today --wait`.
+///
+/// Puzzles unlock at midnight in the timezone of the puzzle server. Time is read through a [`Clock`],
+/// so the countdown and the retries can be tested without waiting.
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Datelike, Utc};
+
+use crate::template::{Day, all_days};
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Source of the current time.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The clock of the system.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Returns the next puzzle that unlocks after `now`.
+pub fn next_unlock(now: DateTime<Utc>) -> Option<Day> {
+    let year = u16::try_from(now.year()).ok()?;
+
+    [year, year + 1]
+        .into_iter()
+        .flat_map(all_days)
+        .find(|day| day.unlocks_at() > now)
+}
+
+/// Counts down to the unlock of `day` in the terminal and returns once it is unlocked.
+pub fn wait_for(clock: &impl Clock, day: Day) {
+    let unlock = day.unlocks_at();
+
+    while let Ok(remaining) = (unlock - clock.now()).to_std() {
+        if remaining.is_zero() {
+            break;
+        }
+        print!(
+            "\r⏳ Day {day} of {} unlocks in {}.",
+            day.year(),
+            format_countdown(remaining)
+        );
+        let _ = io::stdout().flush();
+        clock.sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    println!("\r🔓 Day {day} of {} is unlocked.          ", day.year());
+}
+
+/// Calls `f` up to `attempts` times until it succeeds, doubling the wait between attempts.
+pub fn retry<T, E: Display>(
+    clock: &impl Clock,
+    attempts: u32,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < attempts => {
+                eprintln!(
+                    "Attempt {attempt} failed: {e}. Retrying in {}s.",
+                    backoff.as_secs()
+                );
+                clock.sleep(backoff);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Formats the time left until an unlock, e.g. `2d 03:04:05`.
+fn format_countdown(remaining: Duration) -> String {
+    // NOTE: round up, so the countdown shows `00:00:01` in its last second.
+    let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+
+    match seconds / 86400 {
+        0 => time,
+        days => format!("{days}d {time}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A clock that advances when sleeping, for tests of code that waits.
+#[cfg(feature = "test_lib")]
+pub(crate) struct MockClock {
+    now: std::cell::Cell<DateTime<Utc>>,
+    pub sleeps: std::cell::Cell<u32>,
+}
+
+#[cfg(feature = "test_lib")]
+impl MockClock {
+    pub fn at(now: DateTime<Utc>) -> Self {
+        Self {
+            now: std::cell::Cell::new(now),
+            sleeps: std::cell::Cell::new(0),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+        self.sleeps.set(self.sleeps.get() + 1);
+    }
+}
+
+/// A point in time in UTC, for tests.
+#[cfg(feature = "test_lib")]
+pub(crate) fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    use chrono::TimeZone;
+
+    Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+        .unwrap()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Clock, MockClock, format_countdown, next_unlock, retry, utc, wait_for};
+    use crate::template::Day;
+
+    fn day(year: u16, day: u8) -> Day {
+        Day::of_year(year, day).unwrap()
+    }
+
+    #[test]
+    fn unlocks_at_midnight_of_server() {
+        assert_eq!(day(2024, 1).unlocks_at(), utc(2024, 12, 1, 5, 0));
+        assert_eq!(Day::today_at(utc(2024, 12, 1, 4, 59)), None);
+        assert_eq!(Day::today_at(utc(2024, 12, 1, 5, 0)), Some(day(2024, 1)));
+        assert_eq!(Day::today_at(utc(2025, 12, 13, 5, 0)), None);
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        assert_eq!(next_unlock(utc(2024, 7, 1, 0, 0)), Some(day(2024, 1)));
+        assert_eq!(next_unlock(utc(2024, 12, 1, 5, 0)), Some(day(2024, 2)));
+        assert_eq!(next_unlock(utc(2024, 12, 3, 4, 0)), Some(day(2024, 3)));
+        assert_eq!(next_unlock(utc(2025, 12, 12, 5, 0)), Some(day(2026, 1)));
+        assert_eq!(next_unlock(utc(2025, 12, 31, 23, 0)), Some(day(2026, 1)));
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let clock = MockClock::at(utc(2024, 12, 1, 4, 59));
+        wait_for(&clock, day(2024, 1));
+        assert_eq!(clock.now(), utc(2024, 12, 1, 5, 0));
+        assert_eq!(clock.sleeps.get(), 60);
+
+        wait_for(&clock, day(2024, 1));
+        assert_eq!(clock.sleeps.get(), 60);
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let clock = MockClock::at(utc(2024, 12, 1, 5, 0));
+        let mut calls = 0;
+        let result = retry(&clock, 5, || {
+            calls += 1;
+            if calls < 4 { Err("not yet") } else { Ok(calls) }
+        });
+        assert_eq!(result, Ok(4));
+        assert_eq!(
+            clock.now(),
+            utc(2024, 12, 1, 5, 0) + Duration::from_secs(1 + 2 + 4)
+        );
+
+        let result: Result<(), _> = retry(&clock, 3, || Err("never"));
+        assert_eq!(result, Err("never"));
+        assert_eq!(clock.sleeps.get(), 5);
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_millis(500)), "00:00:01");
+        assert_eq!(format_countdown(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86400 + 5)),
+            "2d 00:00:05"
+        );
+    }
+}